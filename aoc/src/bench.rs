@@ -0,0 +1,241 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::days::{Day, RunError};
+
+/// The timings of one step of one day over several runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    /// `parse`, `part1` or `part2`.
+    pub step: String,
+    pub iterations: u32,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "table" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "day,step,iterations,min_ns,median_ns,mean_ns";
+
+/// Runs the parser and both parts of `day` on `input`, `iterations` times.
+pub fn measure(day: &Day, input: &str, iterations: u32) -> Result<Vec<Measurement>, RunError> {
+    let mut runs = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations.max(1) {
+        runs.push(day.run(input, &[1, 2])?);
+    }
+    // Every run has the same steps, in the same order.
+    let measurements = (0..runs[0].len())
+        .map(|i| {
+            let mut durations = runs.iter().map(|run| run[i].duration).collect::<Vec<_>>();
+            durations.sort_unstable();
+            let total = durations.iter().sum::<Duration>();
+            Measurement {
+                day: day.number,
+                step: runs[0][i].step.to_string(),
+                iterations: durations.len() as u32,
+                min_ns: durations[0].as_nanos() as u64,
+                median_ns: durations[durations.len() / 2].as_nanos() as u64,
+                mean_ns: (total / durations.len() as u32).as_nanos() as u64,
+            }
+        })
+        .collect();
+    Ok(measurements)
+}
+
+pub fn render(measurements: &[Measurement], format: Format) -> String {
+    match format {
+        Format::Json => serde_json::to_string_pretty(measurements).unwrap() + "\n",
+        Format::Csv => {
+            let mut result = format!("{CSV_HEADER}\n");
+            for m in measurements {
+                result += &format!(
+                    "{},{},{},{},{},{}\n",
+                    m.day, m.step, m.iterations, m.min_ns, m.median_ns, m.mean_ns
+                );
+            }
+            result
+        }
+        Format::Table => {
+            let mut result = format!(
+                "Day  Step   {:>12}  {:>12}  {:>12}\n",
+                "Min (µs)", "Median (µs)", "Mean (µs)"
+            );
+            result += &"-".repeat(52);
+            result.push('\n');
+            for m in measurements {
+                result += &format!(
+                    "{:>3}  {:5}  {:>12.1}  {:>12.1}  {:>12.1}\n",
+                    m.day,
+                    m.step,
+                    m.min_ns as f64 / 1000.0,
+                    m.median_ns as f64 / 1000.0,
+                    m.mean_ns as f64 / 1000.0
+                );
+            }
+            result
+        }
+    }
+}
+
+/// Reads measurements saved as JSON or CSV by `render`.
+pub fn parse(text: &str) -> Result<Vec<Measurement>, String> {
+    if !text.starts_with(CSV_HEADER) {
+        return serde_json::from_str(text).map_err(|e| e.to_string());
+    }
+    text.lines()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let invalid = || format!("line {}: expected {CSV_HEADER}", index + 1);
+            let fields = line.trim_end().split(',').collect::<Vec<_>>();
+            let [day, step, iterations, min_ns, median_ns, mean_ns] = fields[..] else {
+                return Err(invalid());
+            };
+            Ok(Measurement {
+                day: day.parse().map_err(|_| invalid())?,
+                step: step.to_string(),
+                iterations: iterations.parse().map_err(|_| invalid())?,
+                min_ns: min_ns.parse().map_err(|_| invalid())?,
+                median_ns: median_ns.parse().map_err(|_| invalid())?,
+                mean_ns: mean_ns.parse().map_err(|_| invalid())?,
+            })
+        })
+        .collect()
+}
+
+pub fn load(path: &Path) -> Result<Vec<Measurement>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    parse(&text).map_err(|e| format!("invalid baseline {}: {e}", path.display()))
+}
+
+/// The median time of a step now and in the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub step: String,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Comparison {
+    /// The relative change, `0.1` meaning 10% slower than the baseline.
+    pub fn change(&self) -> f64 {
+        self.current_ns as f64 / self.baseline_ns.max(1) as f64 - 1.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Pairs up the steps measured both now and in the baseline.
+pub fn compare(baseline: &[Measurement], current: &[Measurement]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|m| {
+            let old = baseline
+                .iter()
+                .find(|old| old.day == m.day && old.step == m.step)?;
+            Some(Comparison {
+                day: m.day,
+                step: m.step.clone(),
+                baseline_ns: old.median_ns,
+                current_ns: m.median_ns,
+            })
+        })
+        .collect()
+}
+
+pub fn render_comparisons(comparisons: &[Comparison], threshold: f64) -> String {
+    let mut result = format!(
+        "Day  Step   {:>12}  {:>12}  {:>8}\n",
+        "Base (µs)", "Now (µs)", "Change"
+    );
+    result += &"-".repeat(48);
+    result.push('\n');
+    for c in comparisons {
+        let flag = if c.is_regression(threshold) {
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        result += &format!(
+            "{:>3}  {:5}  {:>12.1}  {:>12.1}  {:>+7.1}%{flag}\n",
+            c.day,
+            c.step,
+            c.baseline_ns as f64 / 1000.0,
+            c.current_ns as f64 / 1000.0,
+            c.change() * 100.0
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_measure_and_round_trip() {
+        let input = include_str!("../../day01/src/sample_input.txt");
+        let measurements = measure(days::find(1).unwrap(), input, 3).unwrap();
+        let steps = measurements
+            .iter()
+            .map(|m| m.step.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(steps, ["parse", "part1", "part2"]);
+        assert!(measurements
+            .iter()
+            .all(|m| m.iterations == 3 && m.min_ns <= m.median_ns));
+        for format in [Format::Json, Format::Csv] {
+            assert_eq!(parse(&render(&measurements, format)).unwrap(), measurements);
+        }
+        assert!(parse("day,step,iterations,min_ns,median_ns,mean_ns\n1,parse,2\n").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let measurement = |step: &str, median_ns| Measurement {
+            day: 3,
+            step: step.to_string(),
+            iterations: 1,
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+        };
+        let baseline = [measurement("parse", 1000), measurement("part1", 1000)];
+        let current = [
+            measurement("parse", 1050),
+            measurement("part1", 1500),
+            measurement("part2", 10),
+        ];
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].is_regression(0.1));
+        assert!(comparisons[1].is_regression(0.1));
+        assert!((comparisons[1].change() - 0.5).abs() < 1e-9);
+    }
+}
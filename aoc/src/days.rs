@@ -0,0 +1,150 @@
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use common::{ParseError, Solution, SolveError};
+
+/// Parses an input and solves the given parts of it, in parallel or not.
+type Run = fn(&str, &[u8], bool) -> Result<Vec<Timing>, RunError>;
+
+/// Why a day gave no answers: its input didn't parse, or a part has no
+/// answer for it.
+#[derive(Debug)]
+pub enum RunError {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Parse(error) => error.fmt(f),
+            RunError::Solve(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<ParseError> for RunError {
+    fn from(error: ParseError) -> Self {
+        RunError::Parse(error)
+    }
+}
+
+impl From<SolveError> for RunError {
+    fn from(error: SolveError) -> Self {
+        RunError::Solve(error)
+    }
+}
+
+pub struct Day {
+    pub number: u8,
+    run: Run,
+}
+
+/// What a timing was taken of: parsing the input, or solving one part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part(part) => write!(f, "part{part}"),
+        }
+    }
+}
+
+pub struct Timing {
+    pub step: Step,
+    pub answer: String,
+    pub duration: Duration,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            run: run::<S>,
+        }
+    }
+
+    /// Parses the input once, then solves the requested parts from it.
+    /// Parts the day doesn't have are skipped.
+    pub fn run(&self, input: &str, parts: &[u8]) -> Result<Vec<Timing>, RunError> {
+        (self.run)(input, parts, false)
+    }
+
+    /// Same as `run`, solving part 2 on several threads where the day can.
+    pub fn run_parallel(&self, input: &str, parts: &[u8]) -> Result<Vec<Timing>, RunError> {
+        (self.run)(input, parts, true)
+    }
+}
+
+fn run<S: Solution>(input: &str, parts: &[u8], parallel: bool) -> Result<Vec<Timing>, RunError> {
+    let params = S::Params::default();
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let mut timings = vec![Timing {
+        step: Step::Parse,
+        answer: String::new(),
+        duration: start.elapsed(),
+    }];
+    for &part in parts {
+        let timing = match part {
+            1 => time(Step::Part(1), || S::part1(&parsed, &params)),
+            2 if S::HAS_PART2 && parallel => {
+                time(Step::Part(2), || S::part2_parallel(&parsed, &params))
+            }
+            2 if S::HAS_PART2 => time(Step::Part(2), || S::part2(&parsed, &params)),
+            _ => continue,
+        };
+        timings.push(timing?);
+    }
+    Ok(timings)
+}
+
+fn time<A: Display>(
+    step: Step,
+    solve: impl FnOnce() -> Result<A, SolveError>,
+) -> Result<Timing, SolveError> {
+    let start = Instant::now();
+    let answer = solve()?;
+    let duration = start.elapsed();
+    Ok(Timing {
+        step,
+        answer: answer.to_string(),
+        duration,
+    })
+}
+
+pub const DAYS: [Day; 21] = [
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+    Day::of::<day05::Day05>(),
+    Day::of::<day06::Day06>(),
+    Day::of::<day07::Day07>(),
+    Day::of::<day08::Day08>(),
+    Day::of::<day09::Day09>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day13::Day13>(),
+    Day::of::<day14::Day14>(),
+    Day::of::<day15::Day15>(),
+    Day::of::<day16::Day16>(),
+    Day::of::<day17::Day17>(),
+    Day::of::<day18::Day18>(),
+    Day::of::<day20::Day20>(),
+    Day::of::<day21::Day21>(),
+    Day::of::<day25::Day25>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
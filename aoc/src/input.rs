@@ -0,0 +1,66 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Where the puzzle input of a day is read from.
+pub enum Source {
+    /// `dayNN/src/input.txt`, relative to the working directory.
+    Default,
+    /// A single file, only meaningful when running one day.
+    File(PathBuf),
+    /// The standard input, only meaningful when running one day.
+    Stdin,
+    /// A directory holding one `dayNN.txt` file per day.
+    Dir(PathBuf),
+}
+
+impl Source {
+    pub fn parse(value: &str) -> Self {
+        if value == "-" {
+            Self::Stdin
+        } else {
+            Self::File(value.into())
+        }
+    }
+
+    pub fn is_single(&self) -> bool {
+        matches!(self, Self::File(_) | Self::Stdin)
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, String> {
+        match self {
+            Self::Default => read_file(&Path::new(&format!("day{day:02}")).join("src/input.txt")),
+            Self::File(path) => read_file(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("cannot read input from stdin: {e}"))?;
+                Ok(input)
+            }
+            Self::Dir(dir) => read_file(&dir.join(format!("day{day:02}.txt"))),
+        }
+    }
+
+    /// The input of a day as a stream, for the days that can solve it
+    /// without holding all of it.
+    pub fn open(&self, day: u8) -> Result<Box<dyn BufRead>, String> {
+        match self {
+            Self::Default => open_file(&Path::new(&format!("day{day:02}")).join("src/input.txt")),
+            Self::File(path) => open_file(path),
+            Self::Stdin => Ok(Box::new(std::io::stdin().lock())),
+            Self::Dir(dir) => open_file(&dir.join(format!("day{day:02}.txt"))),
+        }
+    }
+}
+
+fn open_file(path: &Path) -> Result<Box<dyn BufRead>, String> {
+    let file = File::open(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))
+}
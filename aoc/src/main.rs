@@ -0,0 +1,675 @@
+mod bench;
+mod days;
+mod input;
+mod stream;
+mod verify;
+mod visualize;
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use days::{Day, Step, DAYS};
+use rayon::prelude::*;
+use verify::{Check, Manifest, Status};
+
+const USAGE: &str = "\
+usage: aoc run <day|all> [--part <1|2>] [--input <file|->] [--input-dir <dir>] [--parallel]
+       aoc run <day> --visualize <ascii|ansi|ppm|png> [--part <1|2>] [--input <file|->]
+               [--every <n>] [--delay <ms>] [--frames-dir <dir>] [--scale <n>]
+       aoc run <day> --stream [--part <1|2>] [--input <file|->]
+       aoc verify [--manifest <file>]
+       aoc bench <day|all> [--input-dir <dir>] [--iterations <n>] [--format <table|json|csv>]
+                 [--output <file>] [--baseline <file>] [--threshold <percent>]
+       aoc generate <day> [--seed <n>] [--size <n>]";
+
+const DEFAULT_MANIFEST: &str = "answers.toml";
+const DEFAULT_ITERATIONS: u32 = 10;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+enum Selection {
+    All,
+    Day(u8),
+}
+
+enum Command {
+    Run(Run),
+    /// Checks the answers against the manifest at this path.
+    Verify(PathBuf),
+    Bench(Bench),
+    Generate(Generate),
+}
+
+struct Run {
+    selection: Selection,
+    part: Option<u8>,
+    input: input::Source,
+    /// Records the frames of a day instead of printing its answers.
+    visualize: Option<visual::Options>,
+    /// Solves the day reading its input line by line, instead of loading
+    /// all of it first.
+    stream: bool,
+    /// Runs the days at the same time, and their slowest parts on several
+    /// threads.
+    parallel: bool,
+}
+
+struct Bench {
+    selection: Selection,
+    input: input::Source,
+    iterations: u32,
+    format: bench::Format,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    /// How much slower than the baseline a step may get, in percent.
+    threshold: f64,
+}
+
+/// Prints a random input for a day.
+struct Generate {
+    generator: &'static inputgen::Generator,
+    seed: u64,
+    size: usize,
+}
+
+struct Row {
+    day: u8,
+    step: Step,
+    answer: String,
+    duration: Duration,
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match parse_args(&args) {
+        Ok(Command::Run(command)) => {
+            let result = match &command.visualize {
+                Some(options) => run_visualization(&command, options),
+                None if command.stream => run_stream(&command).map(|rows| print_report(&rows)),
+                None => run(&command).map(|rows| print_report(&rows)),
+            };
+            if let Err(message) = result {
+                exit_with(&message);
+            }
+        }
+        Ok(Command::Verify(path)) => match Manifest::load(&path) {
+            Ok(manifest) => {
+                let checks = manifest.verify();
+                print_checks(&checks);
+                if checks
+                    .iter()
+                    .any(|check| matches!(check.status, Status::Fail { .. }))
+                {
+                    std::process::exit(1);
+                }
+            }
+            Err(message) => exit_with(&message),
+        },
+        Ok(Command::Bench(command)) => match run_bench(&command) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(message) => exit_with(&message),
+        },
+        Ok(Command::Generate(command)) => {
+            print!("{}", command.generator.generate(command.seed, command.size))
+        }
+        Err(message) => exit_with(&format!("{message}\n{USAGE}")),
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1)
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+    match args.next() {
+        Some("run") => parse_run_args(args).map(Command::Run),
+        Some("verify") => parse_verify_args(args).map(Command::Verify),
+        Some("bench") => parse_bench_args(args).map(Command::Bench),
+        Some("generate") => parse_generate_args(args).map(Command::Generate),
+        Some(other) => Err(format!("unknown command: {other}")),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_verify_args<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<PathBuf, String> {
+    let mut manifest = PathBuf::from(DEFAULT_MANIFEST);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--manifest" | "-m" => {
+                manifest = args.next().ok_or("missing value for --manifest")?.into();
+            }
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    Ok(manifest)
+}
+
+fn parse_selection(arg: Option<&str>) -> Result<Selection, String> {
+    match arg {
+        Some("all") => Ok(Selection::All),
+        Some(day) => Ok(Selection::Day(
+            day.parse().map_err(|_| format!("invalid day: {day}"))?,
+        )),
+        None => Err("missing day".to_string()),
+    }
+}
+
+fn parse_bench_args<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Bench, String> {
+    let mut bench = Bench {
+        selection: parse_selection(args.next())?,
+        input: input::Source::Default,
+        iterations: DEFAULT_ITERATIONS,
+        format: bench::Format::Table,
+        output: None,
+        baseline: None,
+        threshold: DEFAULT_THRESHOLD,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {arg}"));
+        match arg {
+            "--input" | "-i" => bench.input = input::Source::parse(value()?),
+            "--input-dir" => bench.input = input::Source::Dir(value()?.into()),
+            "--iterations" | "-n" => {
+                let value = value()?;
+                bench.iterations = match value.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid iteration count: {value}")),
+                };
+            }
+            "--format" | "-f" => {
+                let value = value()?;
+                bench.format =
+                    bench::Format::parse(value).ok_or(format!("invalid format: {value}"))?;
+            }
+            "--output" | "-o" => bench.output = Some(value()?.into()),
+            "--baseline" | "-b" => bench.baseline = Some(value()?.into()),
+            "--threshold" | "-t" => {
+                let value = value()?;
+                bench.threshold = match value.parse() {
+                    Ok(t) if t >= 0.0 => t,
+                    _ => return Err(format!("invalid threshold: {value}")),
+                };
+            }
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    if bench.input.is_single() && matches!(bench.selection, Selection::All) {
+        return Err("--input needs a single day, use --input-dir with all".to_string());
+    }
+    Ok(bench)
+}
+
+fn parse_generate_args<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Generate, String> {
+    let day = args.next().ok_or("missing day")?;
+    let generator = day
+        .parse()
+        .ok()
+        .and_then(inputgen::find)
+        .ok_or(format!("no generator for day {day}"))?;
+    let mut command = Generate {
+        generator,
+        seed: 0,
+        size: generator.default_size,
+    };
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {arg}"))?;
+        match arg {
+            "--seed" | "-s" => {
+                command.seed = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed: {value}"))?
+            }
+            "--size" => {
+                command.size = value
+                    .parse()
+                    .map_err(|_| format!("invalid size: {value}"))?
+            }
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    Ok(command)
+}
+
+fn parse_run_args<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Run, String> {
+    let selection = parse_selection(args.next())?;
+    let mut part = None;
+    let mut input = input::Source::Default;
+    let mut format = None;
+    let mut stream = false;
+    let mut parallel = false;
+    let mut options = visual::Options::default();
+    while let Some(arg) = args.next() {
+        let mut number = |name: &str| {
+            let value = args.next().ok_or(format!("missing value for {arg}"))?;
+            match value.parse() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(format!("invalid {name}: {value}")),
+            }
+        };
+        match arg {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("missing value for --part")?;
+                match value.parse() {
+                    Ok(p @ (1 | 2)) => part = Some(p),
+                    _ => return Err(format!("invalid part: {value}")),
+                }
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or("missing value for --input")?;
+                input = input::Source::parse(value);
+            }
+            "--input-dir" => {
+                let value = args.next().ok_or("missing value for --input-dir")?;
+                input = input::Source::Dir(value.into());
+            }
+            "--visualize" | "-v" => {
+                let value = args.next().ok_or("missing value for --visualize")?;
+                format = Some(
+                    visual::Format::parse(value)
+                        .ok_or(format!("invalid visualization format: {value}"))?,
+                );
+            }
+            "--stream" | "-s" => stream = true,
+            "--parallel" | "-j" => parallel = true,
+            "--every" => options.every = number("frame interval")?,
+            "--delay" => {
+                let value = args.next().ok_or("missing value for --delay")?;
+                let millis = value
+                    .parse()
+                    .map_err(|_| format!("invalid delay: {value}"))?;
+                options.delay = Duration::from_millis(millis);
+            }
+            "--scale" => options.scale = number("scale")?,
+            "--frames-dir" => {
+                let value = args.next().ok_or("missing value for --frames-dir")?;
+                options.dir = value.into();
+            }
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    if input.is_single() && matches!(selection, Selection::All) {
+        return Err("--input needs a single day, use --input-dir with all".to_string());
+    }
+    if format.is_some() && matches!(selection, Selection::All) {
+        return Err("--visualize needs a single day".to_string());
+    }
+    if stream && matches!(selection, Selection::All) {
+        return Err("--stream needs a single day".to_string());
+    }
+    if stream && format.is_some() {
+        return Err("--stream and --visualize cannot be combined".to_string());
+    }
+    Ok(Run {
+        selection,
+        part,
+        input,
+        visualize: format.map(|format| visual::Options { format, ..options }),
+        stream,
+        parallel,
+    })
+}
+
+fn select_days(selection: &Selection) -> Result<Vec<&'static Day>, String> {
+    match *selection {
+        Selection::All => Ok(DAYS.iter().collect()),
+        Selection::Day(number) => Ok(vec![
+            days::find(number).ok_or(format!("day {number} is not solved"))?
+        ]),
+    }
+}
+
+fn run(command: &Run) -> Result<Vec<Row>, String> {
+    let days = select_days(&command.selection)?;
+    let parts = match command.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let run_day = |day: &Day| -> Result<Vec<Row>, String> {
+        let input = command.input.load(day.number)?;
+        let timings = if command.parallel {
+            day.run_parallel(&input, &parts)
+        } else {
+            day.run(&input, &parts)
+        };
+        let timings = timings.map_err(|e| e.to_string())?;
+        Ok(timings
+            .into_iter()
+            .map(|timing| Row {
+                day: day.number,
+                step: timing.step,
+                answer: timing.answer,
+                duration: timing.duration,
+            })
+            .collect())
+    };
+    // Either way the rows come in the order of the days.
+    let rows = if command.parallel {
+        days.into_par_iter()
+            .map(run_day)
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        days.into_iter()
+            .map(run_day)
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    Ok(rows.into_iter().flatten().collect())
+}
+
+fn run_visualization(command: &Run, options: &visual::Options) -> Result<(), String> {
+    let Selection::Day(day) = command.selection else {
+        unreachable!("checked by parse_run_args");
+    };
+    let input = command.input.load(day)?;
+    visualize::run(day, &input, command.part.unwrap_or(1), options)
+}
+
+/// Both parts come from the same pass over the input, so the whole time is
+/// reported on the first part shown.
+fn run_stream(command: &Run) -> Result<Vec<Row>, String> {
+    let Selection::Day(day) = command.selection else {
+        unreachable!("checked by parse_run_args");
+    };
+    let mut reader = command.input.open(day)?;
+    let start = Instant::now();
+    let answers = stream::run(day, &mut reader)?;
+    let mut duration = start.elapsed();
+    let mut rows = vec![];
+    for (part, answer) in (1..).zip(answers) {
+        if command.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        rows.push(Row {
+            day,
+            step: Step::Part(part),
+            answer,
+            duration: std::mem::take(&mut duration),
+        });
+    }
+    Ok(rows)
+}
+
+/// Measures the selected days, and compares them with the baseline if any.
+/// Returns whether no step got slower than the threshold allows.
+fn run_bench(command: &Bench) -> Result<bool, String> {
+    let baseline = command.baseline.as_deref().map(bench::load).transpose()?;
+    let mut measurements = vec![];
+    for day in select_days(&command.selection)? {
+        let input = command.input.load(day.number)?;
+        measurements
+            .extend(bench::measure(day, &input, command.iterations).map_err(|e| e.to_string())?);
+    }
+    let report = bench::render(&measurements, command.format);
+    match &command.output {
+        Some(path) => std::fs::write(path, report)
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?,
+        None => print!("{report}"),
+    }
+    let Some(baseline) = baseline else {
+        return Ok(true);
+    };
+    let threshold = command.threshold / 100.0;
+    let comparisons = bench::compare(&baseline, &measurements);
+    // Keep stdout machine-readable when it holds the measurements.
+    let comparison_report = bench::render_comparisons(&comparisons, threshold);
+    if command.output.is_none() && command.format != bench::Format::Table {
+        eprint!("{comparison_report}");
+    } else {
+        print!("{comparison_report}");
+    }
+    Ok(!comparisons.iter().any(|c| c.is_regression(threshold)))
+}
+
+fn print_report(rows: &[Row]) {
+    let answer_width = rows
+        .iter()
+        .flat_map(|row| row.answer.lines())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    println!(
+        "Day   Part  {:answer_width$}  {:>12}",
+        "Answer", "Time (µs)"
+    );
+    println!("{}", "-".repeat(answer_width + 26));
+    for row in rows {
+        let mut lines = row.answer.lines();
+        let first = lines.next().unwrap_or("");
+        let step = match row.step {
+            Step::Parse => "parse".to_string(),
+            Step::Part(part) => part.to_string(),
+        };
+        println!(
+            "{:>3}  {step:>5}  {first:answer_width$}  {:>12}",
+            row.day,
+            row.duration.as_micros()
+        );
+        for line in lines {
+            println!("            {line}");
+        }
+    }
+    let total = rows.iter().map(|row| row.duration).sum::<Duration>();
+    println!("{}", "-".repeat(answer_width + 26));
+    println!(
+        "Total{:>width$}",
+        total.as_micros(),
+        width = answer_width + 21
+    );
+}
+
+fn print_checks(checks: &[Check]) {
+    println!("Input         Day  Part  Status");
+    println!("{}", "-".repeat(36));
+    for check in checks {
+        let status = match &check.status {
+            Status::Pass => "pass".to_string(),
+            Status::Fail { expected, actual } => {
+                format!("FAIL: expected {expected:?}, got {actual:?}")
+            }
+            Status::Missing(reason) => format!("missing: {reason}"),
+        };
+        println!(
+            "{:12}  {:>3}  {:>4}  {status}",
+            check.input, check.day, check.part
+        );
+    }
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    println!("{}", "-".repeat(36));
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(|s| matches!(s, Status::Pass)),
+        count(|s| matches!(s, Status::Fail { .. })),
+        count(|s| matches!(s, Status::Missing(_)))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn run_args(line: &str) -> Run {
+        match parse_args(&args(line)) {
+            Ok(Command::Run(command)) => command,
+            _ => panic!("not a run command: {line}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let command = run_args("run 14 --part 2");
+        assert!(matches!(command.selection, Selection::Day(14)));
+        assert_eq!(command.part, Some(2));
+        let command = run_args("run all");
+        assert!(matches!(command.selection, Selection::All));
+        assert_eq!(command.part, None);
+        assert!(parse_args(&args("run 14 --part 3")).is_err());
+        assert!(parse_args(&args("walk 14")).is_err());
+        let command = run_args("run 3 --input -");
+        assert!(matches!(command.input, input::Source::Stdin));
+        let command = run_args("run all --input-dir inputs");
+        assert!(matches!(command.input, input::Source::Dir(_)));
+        assert!(parse_args(&args("run all --input day01.txt")).is_err());
+        let command = run_args("run 14 --visualize png --every 100 --frames-dir out");
+        let options = command.visualize.unwrap();
+        assert_eq!(options.format, visual::Format::Png);
+        assert_eq!((options.every, options.scale), (100, 4));
+        assert_eq!(options.dir, std::path::Path::new("out"));
+        assert!(run_args("run 14 --every 100").visualize.is_none());
+        assert!(parse_args(&args("run 14 --visualize gif")).is_err());
+        assert!(parse_args(&args("run 14 --visualize ascii --every 0")).is_err());
+        assert!(parse_args(&args("run all --visualize ascii")).is_err());
+        assert!(run_args("run 9 --stream --input -").stream);
+        assert!(!run_args("run 9").stream);
+        assert!(run_args("run all --input-dir inputs --parallel").parallel);
+        assert!(parse_args(&args("run all --stream")).is_err());
+        assert!(parse_args(&args("run 9 --stream --visualize ascii")).is_err());
+        assert!(matches!(
+            parse_args(&args("verify")),
+            Ok(Command::Verify(path)) if path == std::path::Path::new("answers.toml")
+        ));
+        assert!(matches!(
+            parse_args(&args("verify -m other.toml")),
+            Ok(Command::Verify(path)) if path == std::path::Path::new("other.toml")
+        ));
+        assert!(parse_args(&args("verify --part 1")).is_err());
+        match parse_args(&args(
+            "bench all -n 5 --format csv --baseline base.csv -t 25",
+        )) {
+            Ok(Command::Bench(bench)) => {
+                assert!(matches!(bench.selection, Selection::All));
+                assert_eq!(bench.iterations, 5);
+                assert_eq!(bench.format, bench::Format::Csv);
+                assert!(bench.baseline.is_some() && bench.output.is_none());
+                assert_eq!(bench.threshold, 25.0);
+            }
+            _ => panic!("expected a bench command"),
+        }
+        assert!(parse_args(&args("bench 3 --iterations 0")).is_err());
+        assert!(parse_args(&args("bench 3 --format xml")).is_err());
+        match parse_args(&args("generate 16 --seed 3")) {
+            Ok(Command::Generate(generate)) => {
+                assert_eq!(generate.generator.day, 16);
+                assert_eq!((generate.seed, generate.size), (3, 55));
+            }
+            _ => panic!("expected a generate command"),
+        }
+        assert!(parse_args(&args("generate 19")).is_err());
+    }
+
+    #[test]
+    fn test_run_single_part() {
+        let rows = run(&Run {
+            selection: Selection::Day(1),
+            part: Some(2),
+            input: input::Source::File("../day01/src/sample_input.txt".into()),
+            visualize: None,
+            stream: false,
+            parallel: false,
+        })
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].day, rows[0].step), (1, Step::Parse));
+        assert_eq!((rows[1].day, rows[1].step), (1, Step::Part(2)));
+        assert_eq!(rows[1].answer, "45000");
+        assert!(run(&Run {
+            selection: Selection::Day(19),
+            part: None,
+            input: input::Source::Default,
+            visualize: None,
+            stream: false,
+            parallel: false,
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let dir = std::env::temp_dir().join("aoc-parallel");
+        std::fs::create_dir_all(&dir).unwrap();
+        for day in DAYS.iter() {
+            let generator = inputgen::find(day.number).unwrap();
+            let input = generator.generate(7, generator.default_size / 4);
+            std::fs::write(dir.join(format!("day{:02}.txt", day.number)), input).unwrap();
+        }
+        let answers = |parallel| {
+            let rows = run(&Run {
+                selection: Selection::All,
+                part: None,
+                input: input::Source::Dir(dir.clone()),
+                visualize: None,
+                stream: false,
+                parallel,
+            })
+            .unwrap();
+            rows.into_iter()
+                .map(|row| (row.day, row.step, row.answer))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(answers(true), answers(false));
+    }
+
+    #[test]
+    fn test_run_stream() {
+        let rows = run_stream(&Run {
+            selection: Selection::Day(1),
+            part: Some(2),
+            input: input::Source::File("../day01/src/sample_input.txt".into()),
+            visualize: None,
+            stream: true,
+            parallel: false,
+        })
+        .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            (rows[0].step, rows[0].answer.as_str()),
+            (Step::Part(2), "45000")
+        );
+    }
+
+    #[test]
+    fn test_run_generated_inputs() {
+        for day in DAYS.iter() {
+            let generator = inputgen::find(day.number).unwrap();
+            for seed in 0..5 {
+                let input = generator.generate(seed, generator.default_size / 4);
+                if let Err(error) = day.run(&input, &[1, 2]) {
+                    panic!("seed {seed}: {error}\n{input}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_malformed_input() {
+        let path = std::env::temp_dir().join("aoc-malformed-day04.txt");
+        std::fs::write(&path, "2-4,6-8\n2-3,4_5\n").unwrap();
+        let error = run(&Run {
+            selection: Selection::Day(4),
+            part: None,
+            input: input::Source::File(path),
+            visualize: None,
+            stream: false,
+            parallel: false,
+        })
+        .err()
+        .unwrap();
+        assert!(error.starts_with("day 04, line 2, column 5:"), "{error}");
+    }
+
+    #[test]
+    fn test_run_unsolvable_input() {
+        // part 1 has an answer, part 2 needs groups of three rucksacks
+        let input = "abab\ncdcd\n";
+        let day = days::find(3).unwrap();
+        assert_eq!(day.run(input, &[1]).unwrap()[1].answer, "10");
+        let error = day.run(input, &[1, 2]).err().unwrap();
+        assert!(matches!(error, days::RunError::Solve(_)));
+        assert!(error.to_string().starts_with("day 03, part 2:"), "{error}");
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use common::ReadError;
+
+/// Solves the parts of a day in a single pass over its input, giving the
+/// answer of each part in order.
+type Stream = fn(&mut dyn BufRead) -> Result<Vec<String>, ReadError>;
+
+const STREAMS: [(u8, Stream); 6] = [
+    (1, |reader| {
+        let (part1, part2) = day01::solve_reader(reader)?;
+        Ok(vec![part1.to_string(), part2.to_string()])
+    }),
+    (2, |reader| {
+        let (part1, part2) = day02::solve_reader(reader)?;
+        Ok(vec![part1.to_string(), part2.to_string()])
+    }),
+    (4, |reader| {
+        let (part1, part2) = day04::solve_reader(reader)?;
+        Ok(vec![part1.to_string(), part2.to_string()])
+    }),
+    (9, |reader| {
+        let (part1, part2) = day09::solve_reader(reader)?;
+        Ok(vec![part1.to_string(), part2.to_string()])
+    }),
+    (10, |reader| {
+        // Only the last screen is kept, all a real program draws.
+        let mut rows = VecDeque::with_capacity(day10::SCREEN_HEIGHT + 1);
+        let part1 = day10::solve_reader(reader, |row| {
+            rows.push_back(row.to_string());
+            if rows.len() > day10::SCREEN_HEIGHT {
+                rows.pop_front();
+            }
+        })?;
+        Ok(vec![part1.to_string(), rows.into_iter().collect()])
+    }),
+    (25, |reader| Ok(vec![day25::part1_reader(reader)?])),
+];
+
+/// Solves a day reading its input line by line.
+pub fn run(day: u8, reader: &mut dyn BufRead) -> Result<Vec<String>, String> {
+    let stream = STREAMS
+        .iter()
+        .find(|&&(number, _)| number == day)
+        .map(|&(_, stream)| stream)
+        .ok_or(format!("day {day} cannot stream its input"))?;
+    stream(reader).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let input = std::fs::read_to_string("../day01/src/sample_input.txt").unwrap();
+        assert_eq!(run(1, &mut input.as_bytes()).unwrap(), ["24000", "45000"]);
+        let error = run(4, &mut "2-4,6-8\n2-3,4_5\n".as_bytes()).unwrap_err();
+        assert!(error.starts_with("day 04, line 2, column 5:"), "{error}");
+        assert!(run(3, &mut input.as_bytes()).is_err());
+        let input = std::fs::read_to_string("../day10/src/input.txt").unwrap();
+        let screen = include_str!("../../day10/src/part2_result.txt");
+        let answers = run(10, &mut input.as_bytes()).unwrap();
+        assert!(answers[1].lines().eq(screen.lines()), "{}", answers[1]);
+    }
+}
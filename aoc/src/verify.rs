@@ -0,0 +1,229 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::days::{self, Step, DAYS};
+use crate::input;
+
+/// The input id standing for each day's own `dayNN/src/input.txt`.
+pub const DEFAULT_INPUT: &str = "default";
+
+/// The expected answers of the registered inputs, read from a TOML file
+/// holding one `[[answer]]` table per day, part and input.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    /// The directory holding one sub-directory of `dayNN.txt` files per
+    /// input id other than `default`.
+    #[serde(default = "default_inputs_dir")]
+    pub inputs_dir: PathBuf,
+    #[serde(default, rename = "answer")]
+    pub answers: Vec<Answer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub expected: String,
+}
+
+fn default_inputs_dir() -> PathBuf {
+    "inputs".into()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// Nothing to compare: either the answer isn't registered, or there is
+    /// no input or solver to produce it.
+    Missing(String),
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub input: String,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| format!("invalid manifest {}: {e}", path.display()))
+    }
+
+    /// The input ids, in order of first appearance.
+    fn inputs(&self) -> Vec<&str> {
+        let mut inputs = Vec::new();
+        for answer in &self.answers {
+            if !inputs.contains(&answer.input.as_str()) {
+                inputs.push(answer.input.as_str());
+            }
+        }
+        inputs
+    }
+
+    fn expected(&self, input: &str, day: u8, part: u8) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|a| a.input == input && a.day == day && a.part == part)
+            .map(|a| a.expected.as_str())
+    }
+
+    fn source(&self, input: &str) -> input::Source {
+        if input == DEFAULT_INPUT {
+            input::Source::Default
+        } else {
+            input::Source::Dir(self.inputs_dir.join(input))
+        }
+    }
+
+    /// Runs every solved day against every registered input.
+    pub fn verify(&self) -> Vec<Check> {
+        let mut checks = Vec::new();
+        for input in self.inputs() {
+            let source = self.source(input);
+            for day in DAYS.iter() {
+                let expected = |part| self.expected(input, day.number, part);
+                if expected(1).is_none() && expected(2).is_none() {
+                    continue;
+                }
+                let mut check = |part, status| {
+                    checks.push(Check {
+                        input: input.to_string(),
+                        day: day.number,
+                        part,
+                        status,
+                    })
+                };
+                let timings = source
+                    .load(day.number)
+                    .and_then(|text| day.run(&text, &[1, 2]).map_err(|e| e.to_string()));
+                let timings = match timings {
+                    Ok(timings) => timings,
+                    Err(message) => {
+                        for part in [1, 2].into_iter().filter(|&p| expected(p).is_some()) {
+                            check(part, Status::Missing(message.clone()));
+                        }
+                        continue;
+                    }
+                };
+                for timing in timings {
+                    let Step::Part(part) = timing.step else {
+                        continue;
+                    };
+                    let status = match expected(part) {
+                        None => Status::Missing("no expected answer".to_string()),
+                        Some(expected) if expected.trim_end() == timing.answer.trim_end() => {
+                            Status::Pass
+                        }
+                        Some(expected) => Status::Fail {
+                            expected: expected.to_string(),
+                            actual: timing.answer,
+                        },
+                    };
+                    check(part, status);
+                }
+            }
+        }
+        for answer in &self.answers {
+            if days::find(answer.day).is_none() {
+                checks.push(Check {
+                    input: answer.input.clone(),
+                    day: answer.day,
+                    part: answer.part,
+                    status: Status::Missing(format!("day {} is not solved", answer.day)),
+                });
+            }
+        }
+        checks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let dir = std::env::temp_dir().join("aoc-verify");
+        std::fs::create_dir_all(dir.join("sample")).unwrap();
+        std::fs::copy(
+            "../day01/src/sample_input.txt",
+            dir.join("sample").join("day01.txt"),
+        )
+        .unwrap();
+        let manifest = Manifest::parse(&format!(
+            r#"
+            inputs_dir = {dir:?}
+
+            [[answer]]
+            day = 1
+            part = 1
+            input = "sample"
+            expected = "24000"
+
+            [[answer]]
+            day = 1
+            part = 1
+            input = "other"
+            expected = "1"
+
+            [[answer]]
+            day = 4
+            part = 2
+            input = "sample"
+            expected = "4"
+
+            [[answer]]
+            day = 1
+            part = 2
+            input = "sample"
+            expected = "24000"
+
+            [[answer]]
+            day = 19
+            part = 1
+            input = "sample"
+            expected = "33"
+            "#
+        ))
+        .unwrap();
+        assert_eq!(manifest.inputs(), ["sample", "other"]);
+        let checks = manifest.verify();
+        let statuses = checks
+            .iter()
+            .map(|c| (c.input.as_str(), c.day, c.part, &c.status))
+            .collect::<Vec<_>>();
+        assert_eq!(statuses.len(), 5);
+        assert_eq!(statuses[0], ("sample", 1, 1, &Status::Pass));
+        assert_eq!(
+            statuses[1],
+            (
+                "sample",
+                1,
+                2,
+                &Status::Fail {
+                    expected: "24000".to_string(),
+                    actual: "45000".to_string()
+                }
+            )
+        );
+        assert!(
+            matches!(statuses[2], ("sample", 4, 2, Status::Missing(m)) if m.starts_with("cannot read"))
+        );
+        assert!(matches!(statuses[3], ("other", 1, 1, Status::Missing(_))));
+        assert!(matches!(statuses[4], ("sample", 19, 1, Status::Missing(_))));
+    }
+}
@@ -0,0 +1,62 @@
+use visual::{Options, Recorder};
+
+/// Parses an input and records the frames of one of its parts.
+type Visualize = fn(&str, u8, &mut dyn Recorder) -> Result<(), String>;
+
+const VISUALIZATIONS: [(u8, Visualize); 4] = [
+    (9, |input, part, recorder| {
+        let motions = day09::parse(input).map_err(|e| e.to_string())?;
+        let knot_count = if part == 1 { 2 } else { 10 };
+        day09::visualize(&motions, knot_count, recorder).map_err(io_error)
+    }),
+    (12, |input, part, recorder| {
+        let board = day12::parse(input).map_err(|e| e.to_string())?;
+        day12::visualize(&board, part == 2, recorder).map_err(io_error)
+    }),
+    (14, |input, part, recorder| {
+        let paths = day14::parse(input).map_err(|e| e.to_string())?;
+        day14::visualize(&paths, part == 2, recorder).map_err(io_error)
+    }),
+    (17, |input, _, recorder| {
+        let jets = day17::parse(input).map_err(|e| e.to_string())?;
+        day17::visualize(&jets, recorder).map_err(io_error)
+    }),
+];
+
+fn io_error(error: std::io::Error) -> String {
+    format!("cannot record the frames: {error}")
+}
+
+/// Records the visualization of a part of a day on the given input.
+pub fn run(day: u8, input: &str, part: u8, options: &Options) -> Result<(), String> {
+    let visualize = VISUALIZATIONS
+        .iter()
+        .find(|&&(number, _)| number == day)
+        .map(|&(_, visualize)| visualize)
+        .ok_or(format!("day {day} has no visualization"))?;
+    let mut recorder = options.recorder().map_err(io_error)?;
+    visualize(input, part, &mut recorder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let dir = std::env::temp_dir().join("aoc-visualize");
+        let _ = std::fs::remove_dir_all(&dir);
+        let options = Options {
+            format: visual::Format::Ppm,
+            every: 10,
+            dir: dir.clone(),
+            ..Options::default()
+        };
+        let input = std::fs::read_to_string("../day14/src/test_input.txt").unwrap();
+        run(14, &input, 1, &options).unwrap();
+        // 25 frames, the first of every 10 and the last
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 4);
+        assert!(run(14, "1,2 -> 3,4", 1, &options).is_err());
+        assert!(run(1, &input, 1, &options).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+//! The reports of `aoc bench` are meant for other programs: nothing else
+//! may end up on stdout with them, like a solver printing as it goes.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+#[test]
+fn test_bench_json_report() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["bench", "all", "--iterations", "1", "--format", "json"])
+        .current_dir(workspace)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let mut days = report
+        .iter()
+        .map(|measurement| measurement["day"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    days.dedup();
+    assert_eq!(days.len(), 21);
+    // parsing and both parts, but day 25 has a single part
+    assert_eq!(report.len(), 21 * 3 - 1);
+}
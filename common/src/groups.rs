@@ -0,0 +1,65 @@
+use alloc::vec::Vec;
+
+use crate::{ParseError, Source};
+
+/// The groups of lines of the input separated by blank lines, each parsed
+/// by `parse` from its lines.
+///
+/// Lines lose their line ending, `\r\n` included, and trailing whitespace,
+/// and blank lines in a row only separate two groups. The lines are still
+/// slices of the input, so errors about them point at the right place.
+pub fn groups<'a, T, F>(
+    source: Source<'a>,
+    mut parse: F,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+where
+    F: FnMut(&[&'a str]) -> Result<T, ParseError> + 'a,
+{
+    let mut lines = source.text().lines().map(str::trim_end);
+    let mut group = Vec::new();
+    core::iter::from_fn(move || {
+        group.clear();
+        for line in lines.by_ref() {
+            if !line.is_empty() {
+                group.push(line);
+            } else if !group.is_empty() {
+                break;
+            }
+        }
+        (!group.is_empty()).then(|| parse(&group))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_groups() {
+        let input = "\r\n1\r\n2  \r\n\r\n\r\n3\n\n  4\n\n \n";
+        let source = Source::new(1, input);
+        let found = groups(source, |lines| Ok(lines.to_vec()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(found, [vec!["1", "2"], vec!["3"], vec!["  4"]]);
+        let sums = groups(source, |lines| {
+            lines
+                .iter()
+                .map(|line| source.parse::<u8>(line.trim()))
+                .sum::<Result<u8, _>>()
+        });
+        assert_eq!(sums.collect::<Result<Vec<_>, _>>().unwrap(), [3, 3, 4]);
+        let input = "1\n2\n\n3\nx \n";
+        let source = Source::new(1, input);
+        let mut numbers = groups(source, |lines| {
+            lines
+                .iter()
+                .map(|line| source.parse::<u8>(line))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        assert_eq!(numbers.next(), Some(Ok(vec![1, 2])));
+        let error = numbers.next().unwrap().unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (5, 1, "x"));
+    }
+}
@@ -0,0 +1,56 @@
+//! What every day shares: the `Solution` trait, parsing helpers and
+//! vectors. Without the default `std` feature it only needs `alloc`, so
+//! the days built on it can too.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use core::fmt::Display;
+
+mod groups;
+mod parse;
+#[cfg(feature = "std")]
+mod read;
+mod solve;
+mod vec;
+
+pub use groups::groups;
+pub use parse::{ParseError, Source};
+#[cfg(feature = "std")]
+pub use read::{for_each_line, ReadError};
+pub use solve::SolveError;
+pub use vec::{Scalar, Vec2, Vec3};
+
+/// A day of the calendar: how to parse its puzzle input, and how to solve
+/// both parts from the parsed form.
+pub trait Solution {
+    /// Day of the month this puzzle was released.
+    const DAY: u8;
+    /// Day 25 only has one part.
+    const HAS_PART2: bool = true;
+
+    /// The parsed input, possibly borrowing from the input text.
+    type Parsed<'a>;
+    /// Extra values some puzzles need besides their input, like the row
+    /// to scan in day 15. `Default` gives the values for a real input.
+    type Params: Default;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+    /// The parts fail on inputs that parse but have no answer.
+    fn part1(parsed: &Self::Parsed<'_>, params: &Self::Params)
+        -> Result<Self::Answer1, SolveError>;
+    fn part2(parsed: &Self::Parsed<'_>, params: &Self::Params)
+        -> Result<Self::Answer2, SolveError>;
+
+    /// Part 2 on several threads, for the days where it is worth it. The
+    /// answer is always the one `part2` gives.
+    fn part2_parallel(
+        parsed: &Self::Parsed<'_>,
+        params: &Self::Params,
+    ) -> Result<Self::Answer2, SolveError> {
+        Self::part2(parsed, params)
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// A malformed puzzle input: where the problem is, and what was found there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line of the offending text.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: {} (found {:?})",
+            self.day, self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// The whole input of a day. Parsers keep slicing into it, so a fragment
+/// they choke on is enough to tell where it came from.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    day: u8,
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u8, input: &'a str) -> Self {
+        Self { day, input }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.input
+    }
+
+    /// An error about `text`, which must be a slice of the input.
+    pub fn error(&self, text: &str, reason: impl Display) -> ParseError {
+        let offset = text.as_ptr() as usize - self.input.as_ptr() as usize;
+        debug_assert!(offset + text.len() <= self.input.len());
+        let before = &self.input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            day: self.day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// An error about the input ending before it was complete.
+    pub fn eof(&self, reason: impl Display) -> ParseError {
+        self.error(&self.input[self.input.len()..], reason)
+    }
+
+    /// Parses `text` with `FromStr`, like `str::parse`.
+    pub fn parse<T: FromStr>(&self, text: &str) -> Result<T, ParseError> {
+        text.parse()
+            .map_err(|_| self.error(text, format!("expected {}", type_name::<T>())))
+    }
+
+    /// `text` without `prefix`, or an error if it doesn't start with it.
+    pub fn strip_prefix<'b>(&self, text: &'b str, prefix: &str) -> Result<&'b str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, format!("expected {prefix:?}")))
+    }
+
+    /// Checks every character of `text` is `valid`, or points at the
+    /// first one that isn't.
+    pub fn check_chars(
+        &self,
+        text: &str,
+        valid: impl Fn(char) -> bool,
+        reason: impl Display,
+    ) -> Result<(), ParseError> {
+        match text.char_indices().find(|&(_, c)| !valid(c)) {
+            Some((i, c)) => Err(self.error(&text[i..i + c.len_utf8()], reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// `text` split around the first `separator`, or an error if it has none.
+    pub fn split_once<'b>(
+        &self,
+        text: &'b str,
+        separator: &str,
+    ) -> Result<(&'b str, &'b str), ParseError> {
+        text.split_once(separator)
+            .ok_or_else(|| self.error(text, format!("expected {separator:?}")))
+    }
+}
+
+fn type_name<T>() -> &'static str {
+    let name = core::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_location() {
+        let input = "12\r\n34\nab é x\n";
+        let source = Source::new(3, input);
+        let error = source.error(&input[13..14], "bad");
+        assert_eq!((error.line, error.column), (3, 6));
+        assert_eq!(error.text, "x");
+        assert_eq!(source.parse::<u8>(&input[4..6]), Ok(34));
+        let error = source.parse::<u8>(&input[7..9]).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.to_string(),
+            "day 03, line 3, column 1: expected u8 (found \"ab\")"
+        );
+        let error = source.eof("missing line");
+        assert_eq!((error.line, error.column), (4, 1));
+    }
+
+    #[test]
+    fn test_helpers() {
+        let input = "move 3 from 1";
+        let source = Source::new(5, input);
+        assert_eq!(source.strip_prefix(input, "move "), Ok("3 from 1"));
+        assert_eq!(source.split_once(input, " from "), Ok(("move 3", "1")));
+        let error = source.split_once(&input[5..], " to ").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (6, "3 from 1"));
+        let error = source.check_chars(input, |c| c != 'f', "no f").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (8, "f"));
+    }
+}
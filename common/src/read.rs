@@ -0,0 +1,85 @@
+use std::fmt::{self, Display};
+use std::io::{self, BufRead};
+
+use crate::{ParseError, Source};
+
+/// A failure reading an input stream: either reading it, or what it holds.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "cannot read input: {error}"),
+            ReadError::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> Self {
+        ReadError::Parse(error)
+    }
+}
+
+/// Calls `on_line` with each line of `reader`, without its line ending,
+/// and a `Source` of that line. Only one line is held at a time, and the
+/// errors `on_line` returns are moved to the line they are about.
+pub fn for_each_line(
+    day: u8,
+    mut reader: impl BufRead,
+    mut on_line: impl FnMut(Source, &str) -> Result<(), ParseError>,
+) -> Result<(), ReadError> {
+    let mut buffer = String::new();
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        on_line(Source::new(day, line), line).map_err(|mut error| {
+            error.line = number;
+            error
+        })?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_each_line() {
+        let mut lines = vec![];
+        for_each_line(4, "1-2\r\n\n3-4".as_bytes(), |_, line| {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lines, ["1-2", "", "3-4"]);
+        let error = for_each_line(4, "1-2\n3_4\n".as_bytes(), |source, line| {
+            source.split_once(line, "-").map(|_| ())
+        })
+        .unwrap_err();
+        let ReadError::Parse(error) = error else {
+            panic!("expected a parse error, got {error}");
+        };
+        assert_eq!((error.day, error.line, error.column), (4, 2, 1));
+        let error = for_each_line(4, b"1-2\n\xff\n".as_slice(), |_, _| Ok(())).unwrap_err();
+        assert!(matches!(error, ReadError::Io(_)));
+    }
+}
@@ -0,0 +1,33 @@
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+
+/// An input that parsed but has no answer for one of the parts, like
+/// rucksacks that don't split into the groups of day 3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub day: u8,
+    pub part: u8,
+    pub reason: String,
+}
+
+impl SolveError {
+    pub fn new(day: u8, part: u8, reason: impl Display) -> Self {
+        Self {
+            day,
+            part,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, part {}: {}",
+            self.day, self.part, self.reason
+        )
+    }
+}
+
+impl core::error::Error for SolveError {}
@@ -0,0 +1,284 @@
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// The signed integers coordinates are made of.
+pub trait Scalar:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const NEG_ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const NEG_ONE: Self = -1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_scalar!(i8, i16, i32, i64, i128, isize);
+
+/// A 2D position or offset. `y` grows downwards, like lines of text, so
+/// `UP` is `(0, -1)`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Scalar> Vec2<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO);
+    pub const UP: Self = Self::new(T::ZERO, T::NEG_ONE);
+    pub const DOWN: Self = Self::new(T::ZERO, T::ONE);
+    pub const LEFT: Self = Self::new(T::NEG_ONE, T::ZERO);
+    pub const RIGHT: Self = Self::new(T::ONE, T::ZERO);
+    /// Offsets to the positions sharing an edge.
+    pub const NEIGHBORS4: [Self; 4] = [Self::UP, Self::LEFT, Self::RIGHT, Self::DOWN];
+    /// Offsets to the positions sharing an edge or a corner.
+    pub const NEIGHBORS8: [Self; 8] = [
+        Self::new(T::NEG_ONE, T::NEG_ONE),
+        Self::UP,
+        Self::new(T::ONE, T::NEG_ONE),
+        Self::LEFT,
+        Self::RIGHT,
+        Self::new(T::NEG_ONE, T::ONE),
+        Self::DOWN,
+        Self::new(T::ONE, T::ONE),
+    ];
+
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs())
+    }
+
+    /// Each coordinate's sign: a step of at most one in each axis.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// A quarter turn clockwise, as seen on screen: `RIGHT` becomes `DOWN`.
+    pub fn rotate_cw(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// A quarter turn counter-clockwise: `RIGHT` becomes `UP`.
+    pub fn rotate_ccw(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+/// A 3D position or offset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Scalar> Vec3<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO, T::ZERO);
+    pub const ONE: Self = Self::new(T::ONE, T::ONE, T::ONE);
+    pub const X: Self = Self::new(T::ONE, T::ZERO, T::ZERO);
+    pub const Y: Self = Self::new(T::ZERO, T::ONE, T::ZERO);
+    pub const Z: Self = Self::new(T::ZERO, T::ZERO, T::ONE);
+    /// Offsets to the cubes sharing a face.
+    pub const NEIGHBORS6: [Self; 6] = [
+        Self::new(T::NEG_ONE, T::ZERO, T::ZERO),
+        Self::X,
+        Self::new(T::ZERO, T::NEG_ONE, T::ZERO),
+        Self::Y,
+        Self::new(T::ZERO, T::ZERO, T::NEG_ONE),
+        Self::Z,
+    ];
+
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs()).max(d.z.abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// A quarter turn around the z axis, turning `X` into `Y`.
+    pub fn rotate_z(self) -> Self {
+        Self::new(-self.y, self.x, self.z)
+    }
+
+    /// A quarter turn around the x axis, turning `Y` into `Z`.
+    pub fn rotate_x(self) -> Self {
+        Self::new(self.x, -self.z, self.y)
+    }
+
+    /// A quarter turn around the y axis, turning `Z` into `X`.
+    pub fn rotate_y(self) -> Self {
+        Self::new(self.z, self.y, -self.x)
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+macro_rules! impl_ops {
+    ($name:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),* }
+            }
+        }
+    };
+}
+
+impl_ops!(Vec2 { x, y });
+impl_ops!(Vec3 { x, y, z });
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec2() {
+        let a = Vec2::new(1, -2);
+        let b = Vec2::new(4, 2);
+        assert_eq!(a + b, Vec2::new(5, 0));
+        assert_eq!(b - a, Vec2::new(3, 4));
+        assert_eq!(-a * 2, Vec2::new(-2, 4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum(), Vec2::new(1, 1));
+        assert_eq!(Vec2::<i32>::RIGHT.rotate_cw(), Vec2::DOWN);
+        assert_eq!(Vec2::<i32>::RIGHT.rotate_ccw(), Vec2::UP);
+        assert_eq!(a.rotate_cw().rotate_ccw(), a);
+        assert_eq!(a.min(b), Vec2::new(1, -2));
+        assert_eq!(Vec2::from((3i64, 4)).max(Vec2::ZERO), Vec2::new(3, 4));
+    }
+
+    #[test]
+    fn test_vec3() {
+        let a = Vec3::new(1, 2, 3);
+        let mut b = Vec3::new(2, 0, 7);
+        b -= a;
+        assert_eq!(b, Vec3::new(1, -2, 4));
+        b += Vec3::ONE;
+        assert_eq!(b, Vec3::new(2, -1, 5));
+        assert_eq!(a.manhattan(Vec3::ZERO), 6);
+        assert_eq!(a.chebyshev(Vec3::ZERO), 3);
+        assert_eq!(Vec3::<i32>::X.rotate_z(), Vec3::Y);
+        assert_eq!(Vec3::<i32>::Y.rotate_x(), Vec3::Z);
+        assert_eq!(Vec3::<i32>::Z.rotate_y(), Vec3::X);
+        assert_eq!(
+            Vec3::<i32>::NEIGHBORS6
+                .iter()
+                .copied()
+                .fold(Vec3::ZERO, Add::add),
+            Vec3::ZERO
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::cmp::Reverse;
+use std::io::BufRead;
+
+use common::{for_each_line, groups, ParseError, ReadError, Solution, SolveError, Source};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed<'a> = Vec<i32>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(totals: &Vec<i32>, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(totals))
+    }
+
+    fn part2(totals: &Vec<i32>, _: &()) -> Result<i32, SolveError> {
+        Ok(part2(totals))
+    }
+}
+
+/// The calories each elf carries, elves being separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    let source = Source::new(Day01::DAY, input);
+    groups(source, |lines| {
+        lines
+            .iter()
+            .try_fold(0, |total, line| add_calories(source, total, line))
+    })
+    .collect()
+}
+
+fn add_calories(source: Source, total: i32, line: &str) -> Result<i32, ParseError> {
+    total
+        .checked_add(source.parse(line)?)
+        .ok_or_else(|| source.error(line, "too many calories"))
+}
+
+pub fn part1(totals: &[i32]) -> i32 {
+    solve(totals).0
+}
+
+pub fn part2(totals: &[i32]) -> i32 {
+    solve(totals).1
+}
+
+/// Both parts, reading the input a line at a time instead of holding it.
+pub fn solve_reader(reader: impl BufRead) -> Result<(i32, i32), ReadError> {
+    let mut array = [0; 3];
+    let mut elves = Elves::default();
+    for_each_line(Day01::DAY, reader, |source, line| {
+        elves.line(source, line, |cals| ordered_insert(&mut array, cals))
+    })?;
+    elves.finish(|cals| ordered_insert(&mut array, cals));
+    Ok((array[0], array.into_iter().sum()))
+}
+
+fn solve(totals: &[i32]) -> (i32, i32) {
+    let mut array = [0; 3];
+    for &cals in totals {
+        ordered_insert(&mut array, cals);
+    }
+    (array[0], array.into_iter().sum())
+}
+
+/// An elf, by its position in the input, and the calories it carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: i32,
+}
+
+/// The `n` elves carrying the most calories, most first. Elves carrying
+/// as much come in input order.
+pub fn top_elves(totals: &[i32], n: usize) -> Vec<Elf> {
+    let mut elves = totals
+        .iter()
+        .enumerate()
+        .map(|(index, &calories)| Elf { index, calories })
+        .collect::<Vec<_>>();
+    let key = |elf: &Elf| (Reverse(elf.calories), elf.index);
+    if n < elves.len() {
+        // only the top ones need sorting
+        elves.select_nth_unstable_by_key(n, key);
+        elves.truncate(n);
+    }
+    elves.sort_unstable_by_key(key);
+    elves
+}
+
+/// Summary statistics of the elves' totals.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    sorted: Vec<i32>,
+}
+
+/// The elves whose totals are in `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: i64,
+    pub end: i64,
+    pub count: usize,
+}
+
+impl Stats {
+    pub fn new(totals: &[i32]) -> Self {
+        let mut sorted = totals.to_vec();
+        sorted.sort_unstable();
+        Self { sorted }
+    }
+
+    pub fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn min(&self) -> Option<i32> {
+        self.sorted.first().copied()
+    }
+
+    pub fn max(&self) -> Option<i32> {
+        self.sorted.last().copied()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        let sum = self.sorted.iter().map(|&cals| cals as i64).sum::<i64>();
+        (!self.sorted.is_empty()).then(|| sum as f64 / self.count() as f64)
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// The total `p` percent of the way from the smallest to the largest,
+    /// interpolating between the two closest ones.
+    ///
+    /// Panics unless `p` is in `0.0..=100.0`.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        assert!((0.0..=100.0).contains(&p), "invalid percentile: {p}");
+        if self.sorted.is_empty() {
+            return None;
+        }
+        let rank = p / 100.0 * (self.count() - 1) as f64;
+        let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+        let (low_cals, high_cals) = (self.sorted[low] as f64, self.sorted[high] as f64);
+        Some(low_cals + (high_cals - low_cals) * (rank - low as f64))
+    }
+
+    /// `bucket_count` buckets of the same width, the first starting at the
+    /// smallest total and the last holding the largest. None without
+    /// totals.
+    ///
+    /// Panics if `bucket_count` is 0.
+    pub fn histogram(&self, bucket_count: usize) -> Vec<Bucket> {
+        assert!(bucket_count > 0, "a histogram needs buckets");
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return vec![];
+        };
+        let (min, max) = (min as i64, max as i64);
+        let width = (max - min + 1 + bucket_count as i64 - 1) / bucket_count as i64;
+        let mut buckets = (0..bucket_count as i64)
+            .map(|i| Bucket {
+                start: min + i * width,
+                end: min + (i + 1) * width,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+        for &cals in &self.sorted {
+            buckets[((cals as i64 - min) / width) as usize].count += 1;
+        }
+        buckets
+    }
+}
+
+fn ordered_insert<const C: usize>(slice: &mut [i32; C], value: i32) {
+    for i in 0..C {
+        if slice[i] < value {
+            slice.copy_within(i..(C - 1), i + 1);
+            slice[i] = value;
+            return;
+        }
+    }
+}
+
+/// Adds up the calories of each elf, a line at a time, the way `parse`
+/// does.
+#[derive(Default)]
+struct Elves {
+    curr_total: i32,
+    started: bool,
+    /// Whether a blank line came after the current elf's calories.
+    ended: bool,
+}
+
+impl Elves {
+    fn line(
+        &mut self,
+        source: Source,
+        line: &str,
+        mut on_elf: impl FnMut(i32),
+    ) -> Result<(), ParseError> {
+        let line = line.trim_end();
+        if line.is_empty() {
+            self.ended = self.started;
+            return Ok(());
+        }
+        if self.ended {
+            on_elf(self.curr_total);
+            self.curr_total = 0;
+            self.ended = false;
+        }
+        self.started = true;
+        self.curr_total = add_calories(source, self.curr_total, line)?;
+        Ok(())
+    }
+
+    fn finish(self, mut on_elf: impl FnMut(i32)) {
+        if self.started {
+            on_elf(self.curr_total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("input.txt");
+    const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
+
+    #[test]
+    fn test_part1() {
+        let result = part1(&parse(SAMPLE_INPUT).unwrap());
+        assert_eq!(result, 24000);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 68802);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = part2(&parse(SAMPLE_INPUT).unwrap());
+        assert_eq!(result, 45000);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 205370);
+    }
+
+    #[test]
+    fn test_top_elves() {
+        let totals = parse(SAMPLE_INPUT).unwrap();
+        let top = top_elves(&totals, 3)
+            .into_iter()
+            .map(|elf| (elf.index, elf.calories))
+            .collect::<Vec<_>>();
+        assert_eq!(top, [(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(top_elves(&totals, 10).len(), 5);
+        assert!(top_elves(&totals, 0).is_empty());
+        let ties = top_elves(&[5, 7, 5, 7, 5], 3);
+        let indices = ties.iter().map(|elf| elf.index).collect::<Vec<_>>();
+        assert_eq!(indices, [1, 3, 0]);
+        let totals = parse(INPUT).unwrap();
+        let top = top_elves(&totals, 3);
+        assert_eq!(top[0].calories, part1(&totals));
+        assert_eq!(
+            top.iter().map(|elf| elf.calories).sum::<i32>(),
+            part2(&totals)
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&parse(SAMPLE_INPUT).unwrap());
+        assert_eq!(stats.count(), 5);
+        assert_eq!((stats.min(), stats.max()), (Some(4000), Some(24000)));
+        assert_eq!(stats.mean(), Some(11000.0));
+        assert_eq!(stats.median(), Some(10000.0));
+        assert_eq!(stats.percentile(25.0), Some(6000.0));
+        assert_eq!(stats.percentile(90.0), Some(18800.0));
+        assert_eq!(stats.percentile(100.0), Some(24000.0));
+        let counts = stats
+            .histogram(4)
+            .into_iter()
+            .map(|bucket| (bucket.start, bucket.count))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [(4000, 2), (9001, 2), (14002, 0), (19003, 1)]);
+        let empty = Stats::new(&[]);
+        assert_eq!((empty.mean(), empty.median()), (None, None));
+        assert!(empty.histogram(3).is_empty());
+        let same = Stats::new(&[7, 7]);
+        assert_eq!(same.histogram(2)[0].count, 2);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("1000\n2000\n\n3O00\n").unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (1, 4, 1));
+        assert_eq!(error.text, "3O00");
+        assert_eq!(
+            parse("\n1000 \r\n2000\r\n\r\n\r\n3000\t\n\n").unwrap(),
+            [3000, 3000]
+        );
+        // found by `cargo fuzz`
+        let error = parse("2147483647\n1\n").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "1"));
+    }
+
+    #[test]
+    fn test_solve_reader() {
+        for input in [SAMPLE_INPUT, INPUT] {
+            let totals = parse(input).unwrap();
+            let expected = (part1(&totals), part2(&totals));
+            assert_eq!(solve_reader(input.as_bytes()).unwrap(), expected);
+            let crlf = input.lines().map(|line| format!("{line}\r\n"));
+            let crlf = format!("\n\n{}\n\n", crlf.collect::<String>());
+            assert_eq!(solve_reader(crlf.as_bytes()).unwrap(), expected);
+        }
+        let spaced = "\n1000 \r\n2000\r\n\r\n\r\n3000\t\n\n";
+        assert_eq!(solve_reader(spaced.as_bytes()).unwrap(), (3000, 6000));
+        let Err(ReadError::Parse(error)) = solve_reader("1000\n2000\n\n3O00\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (4, 1, "3O00")
+        );
+    }
+}
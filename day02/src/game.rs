@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::Outcome;
+
+/// The rules of a game of shapes in a cycle, like rock-paper-scissors or
+/// rock-paper-scissors-lizard-spock: which shapes there are, the letters
+/// standing for them in a strategy guide, and what a round scores.
+///
+/// The shapes are listed in cycle order, each beating the half of the
+/// others just before it, so there must be an odd number of them.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Game {
+    #[serde(rename = "shape")]
+    pub shapes: Vec<ShapeRule>,
+    pub win: OutcomeRule,
+    pub draw: OutcomeRule,
+    pub loss: OutcomeRule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShapeRule {
+    pub name: String,
+    /// What playing the shape scores, whatever the outcome.
+    pub score: i32,
+    /// The letter in the first column of the guide, the opponent's.
+    pub opponent: char,
+    /// The letter in the second column, when it is a shape to play.
+    pub me: char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutcomeRule {
+    /// The letter in the second column, when it is an outcome to reach.
+    pub letter: char,
+    pub score: i32,
+}
+
+impl Game {
+    /// The game of the puzzle.
+    pub fn rock_paper_scissors() -> Self {
+        let shape = |name: &str, score, opponent, me| ShapeRule {
+            name: name.to_string(),
+            score,
+            opponent,
+            me,
+        };
+        Self {
+            shapes: vec![
+                shape("rock", 1, 'A', 'X'),
+                shape("paper", 2, 'B', 'Y'),
+                shape("scissors", 3, 'C', 'Z'),
+            ],
+            win: OutcomeRule {
+                letter: 'Z',
+                score: 6,
+            },
+            draw: OutcomeRule {
+                letter: 'Y',
+                score: 3,
+            },
+            loss: OutcomeRule {
+                letter: 'X',
+                score: 0,
+            },
+        }
+    }
+
+    /// Reads the rules from TOML, like `rpsls.toml`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let game: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        game.check()?;
+        Ok(game)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| format!("invalid game {}: {e}", path.display()))
+    }
+
+    fn check(&self) -> Result<(), String> {
+        // with a single shape, winning would be a draw
+        if self.shapes.len() < 3 || self.shapes.len().is_multiple_of(2) {
+            return Err(format!(
+                "expected an odd number of shapes, at least 3, found {}",
+                self.shapes.len()
+            ));
+        }
+        let outcomes = [self.win, self.draw, self.loss];
+        let columns = [
+            (
+                "opponent",
+                self.shapes.iter().map(|s| s.opponent).collect::<Vec<_>>(),
+            ),
+            ("me", self.shapes.iter().map(|s| s.me).collect()),
+            ("outcome", outcomes.iter().map(|o| o.letter).collect()),
+        ];
+        for (column, letters) in columns {
+            let mut seen = HashSet::new();
+            if let Some(letter) = letters
+                .into_iter()
+                .find(|&l| l.is_whitespace() || !seen.insert(l))
+            {
+                return Err(format!("{column} letter {letter:?} is blank or used twice"));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn shape_index(&self, letter: char, column: usize) -> Option<usize> {
+        self.shapes.iter().position(|shape| match column {
+            0 => shape.opponent == letter,
+            _ => shape.me == letter,
+        })
+    }
+
+    pub(crate) fn outcome(&self, letter: char) -> Option<Outcome> {
+        [
+            (Outcome::Win, self.win),
+            (Outcome::Draw, self.draw),
+            (Outcome::Loss, self.loss),
+        ]
+        .into_iter()
+        .find(|(_, rule)| rule.letter == letter)
+        .map(|(outcome, _)| outcome)
+    }
+
+    pub(crate) fn outcome_score(&self, outcome: Outcome) -> i32 {
+        match outcome {
+            Outcome::Win => self.win.score,
+            Outcome::Draw => self.draw.score,
+            Outcome::Loss => self.loss.score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let game = Game::parse(include_str!("rock_paper_scissors.toml")).unwrap();
+        assert_eq!(game, Game::rock_paper_scissors());
+        let game = Game::parse(include_str!("rpsls.toml")).unwrap();
+        assert_eq!(game.shapes.len(), 5);
+        assert_eq!(game.shape_index('E', 0), Some(1));
+        assert_eq!(game.shape_index('E', 1), None);
+        assert_eq!(game.outcome('Y'), Some(Outcome::Draw));
+        let rules = include_str!("rock_paper_scissors.toml");
+        let even = format!(
+            "{rules}\n[[shape]]\nname = \"well\"\nscore = 4\nopponent = \"D\"\nme = \"W\"\n"
+        );
+        let error = Game::parse(&even).unwrap_err();
+        assert!(error.contains("odd number"), "{error}");
+        let mut single = Game::rock_paper_scissors();
+        single.shapes.truncate(1);
+        let error = single.check().unwrap_err();
+        assert!(error.contains("at least 3, found 1"), "{error}");
+        let twice = rules.replace("opponent = \"B\"", "opponent = \"A\"");
+        let error = Game::parse(&twice).unwrap_err();
+        assert!(error.contains("'A'"), "{error}");
+        let unknown = rules.replace("score = 3\n", "score = 3\nlizard = 4\n");
+        assert!(Game::parse(&unknown).is_err());
+    }
+}
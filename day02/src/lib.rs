@@ -0,0 +1,409 @@
+use std::io::BufRead;
+
+use common::{for_each_line, ParseError, ReadError, Solution, SolveError, Source};
+
+mod game;
+mod search;
+mod tournament;
+
+pub use game::{Game, OutcomeRule, ShapeRule};
+pub use search::{search, Interpretation, Meaning, RoundScore, Scored, Search};
+pub use tournament::{
+    play_match, tournament, BeatLastMove, Entrant, FixedGuide, FrequencyPredictor, Pairing, Random,
+    Strategy, Tally, Tournament, Turn,
+};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed<'a> = Vec<&'a str>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(lines: &Vec<&str>, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(lines))
+    }
+
+    fn part2(lines: &Vec<&str>, _: &()) -> Result<i32, SolveError> {
+        Ok(part2(lines))
+    }
+}
+
+/// Checks every line is a round like `A Y`. The second column means
+/// different things in each part, so lines are kept as text.
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(Day02::DAY, input);
+    let game = Game::rock_paper_scissors();
+    input
+        .trim()
+        .lines()
+        .map(|line| game.check_round(source, line, &[Reading::Shape, Reading::Outcome]))
+        .collect()
+}
+
+pub fn part1(lines: &[&str]) -> i32 {
+    let game = Game::rock_paper_scissors();
+    solve(lines, |line| line_score::<Shape, Shape, Round>(&game, line))
+}
+
+pub fn part2(lines: &[&str]) -> i32 {
+    let game = Game::rock_paper_scissors();
+    solve(lines, |line| {
+        line_score::<Shape, Outcome, Round>(&game, line)
+    })
+}
+
+fn solve(lines: &[&str], f: impl Fn(&str) -> Option<i32>) -> i32 {
+    lines
+        .iter()
+        .map(|&line| f(line).expect("rounds are checked by parse"))
+        .sum()
+}
+
+/// Both parts, reading the input a line at a time instead of holding it.
+/// Blank lines are skipped.
+pub fn solve_reader(reader: impl BufRead) -> Result<(i32, i32), ReadError> {
+    let game = Game::rock_paper_scissors();
+    let (mut part1, mut part2) = (0, 0);
+    for_each_line(Day02::DAY, reader, |source, line| {
+        if !line.is_empty() {
+            let line = game.check_round(source, line, &[Reading::Shape, Reading::Outcome])?;
+            part1 += line_score::<Shape, Shape, Round>(&game, line).unwrap();
+            part2 += line_score::<Shape, Outcome, Round>(&game, line).unwrap();
+        }
+        Ok(())
+    })?;
+    Ok((part1, part2))
+}
+
+/// How the second column of the strategy guide is read: as the shape to
+/// play, in part 1, or as the outcome to reach, in part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    Shape,
+    Outcome,
+}
+
+impl Game {
+    /// The total score of following the strategy guide in `input`, its
+    /// second column read as `reading` says.
+    pub fn total_score(&self, input: &str, reading: Reading) -> Result<i32, ParseError> {
+        let source = Source::new(Day02::DAY, input);
+        let mut total = 0;
+        for line in input.lines().map(str::trim_end).filter(|l| !l.is_empty()) {
+            let line = self.check_round(source, line, &[reading])?;
+            let score = match reading {
+                Reading::Shape => line_score::<Shape, Shape, Round>(self, line),
+                Reading::Outcome => line_score::<Shape, Outcome, Round>(self, line),
+            };
+            total += score.unwrap();
+        }
+        Ok(total)
+    }
+
+    /// Checks `line` is a round: an opponent's letter, a space, and a
+    /// letter with a meaning for each of the `readings`.
+    fn check_round<'a>(
+        &self,
+        source: Source,
+        line: &'a str,
+        readings: &[Reading],
+    ) -> Result<&'a str, ParseError> {
+        let opponent = |c| self.shape_index(c, 0).is_some();
+        let space = |c| c == ' ';
+        let me = |c| {
+            readings.iter().all(|reading| match reading {
+                Reading::Shape => self.shape_index(c, 1).is_some(),
+                Reading::Outcome => self.outcome(c).is_some(),
+            })
+        };
+        let valid: [&dyn Fn(char) -> bool; 3] = [&opponent, &space, &me];
+        let invalid = line.char_indices().zip(valid).find(|&((_, c), v)| !v(c));
+        match invalid {
+            Some(((i, c), _)) => {
+                Err(source.error(&line[i..i + c.len_utf8()], "unexpected character"))
+            }
+            None if line.chars().count() != 3 => {
+                Err(source.error(line, "expected a round like \"A Y\""))
+            }
+            None => Ok(line),
+        }
+    }
+}
+
+/// The score of a round, the first letter of `line` read as an `A` and the
+/// third one as a `B`; `None` if they don't mean anything in `game`.
+fn line_score<'g, A, B, C>(game: &'g Game, line: &str) -> Option<i32>
+where
+    A: FromLetter<'g>,
+    B: FromLetter<'g>,
+    C: From<(A, B)> + Score,
+{
+    line_round::<A, B, C>(game, line).map(|round| round.score())
+}
+
+/// The round `line` stands for, read the way `line_score` does.
+fn line_round<'g, A, B, C>(game: &'g Game, line: &str) -> Option<C>
+where
+    A: FromLetter<'g>,
+    B: FromLetter<'g>,
+    C: From<(A, B)>,
+{
+    let mut chars = line.chars();
+    let a = A::from_letter(game, chars.next()?, 0)?;
+    let b = B::from_letter(game, chars.nth(1)?, 1)?;
+    Some(C::from((a, b)))
+}
+
+trait Score {
+    fn score(&self) -> i32;
+}
+
+/// A value a letter of a strategy guide stands for, the letter being in
+/// the first column or the second.
+trait FromLetter<'g>: Sized {
+    fn from_letter(game: &'g Game, letter: char, column: usize) -> Option<Self>;
+}
+
+/// A shape of a game: it beats the half of the shapes just before it in
+/// the cycle, and loses against the other half.
+#[derive(Clone, Copy, Debug)]
+pub struct Shape<'g> {
+    game: &'g Game,
+    index: usize,
+}
+
+impl PartialEq for Shape<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.game, other.game) && self.index == other.index
+    }
+}
+
+impl Eq for Shape<'_> {}
+
+impl<'g> Shape<'g> {
+    /// The shape at `index` in the cycle of `game`, if there is one.
+    pub fn new(game: &'g Game, index: usize) -> Option<Self> {
+        (index < game.shapes.len()).then_some(Self { game, index })
+    }
+
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    pub fn name(self) -> &'g str {
+        &self.game.shapes[self.index].name
+    }
+
+    fn count(self) -> usize {
+        self.game.shapes.len()
+    }
+
+    fn nth(self, index: usize) -> Self {
+        Self {
+            game: self.game,
+            index: index % self.count(),
+        }
+    }
+
+    pub fn wins_against(self, other: Self) -> bool {
+        let distance = (self.index + self.count() - other.index) % self.count();
+        (1..=self.count() / 2).contains(&distance)
+    }
+
+    /// The shape right after this one, which beats it.
+    pub fn to_winner(self) -> Self {
+        self.nth(self.index + 1)
+    }
+
+    /// The shape right before this one, which it beats.
+    pub fn to_loser(self) -> Self {
+        self.nth(self.index + self.count() - 1)
+    }
+
+    pub fn play_for_outcome(self, outcome: Outcome) -> Self {
+        use Outcome::*;
+        match outcome {
+            Win => self.to_winner(),
+            Loss => self.to_loser(),
+            Draw => self,
+        }
+    }
+}
+
+impl<'g> FromLetter<'g> for Shape<'g> {
+    fn from_letter(game: &'g Game, letter: char, column: usize) -> Option<Self> {
+        let index = game.shape_index(letter, column)?;
+        Some(Self { game, index })
+    }
+}
+
+impl Score for Shape<'_> {
+    fn score(&self) -> i32 {
+        self.game.shapes[self.index].score
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl<'g> FromLetter<'g> for Outcome {
+    fn from_letter(game: &'g Game, letter: char, column: usize) -> Option<Self> {
+        (column == 1).then(|| game.outcome(letter)).flatten()
+    }
+}
+
+struct Round<'g> {
+    me: Shape<'g>,
+    other: Shape<'g>,
+}
+
+impl<'g> Round<'g> {
+    fn new(me: Shape<'g>, other: Shape<'g>) -> Self {
+        Self { me, other }
+    }
+
+    fn outcome(&self) -> Outcome {
+        if self.me.wins_against(self.other) {
+            Outcome::Win
+        } else if self.other.wins_against(self.me) {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+}
+
+impl Score for Round<'_> {
+    fn score(&self) -> i32 {
+        self.me.score() + self.me.game.outcome_score(self.outcome())
+    }
+}
+
+impl<'g> From<(Shape<'g>, Shape<'g>)> for Round<'g> {
+    fn from((other, me): (Shape<'g>, Shape<'g>)) -> Self {
+        Self::new(me, other)
+    }
+}
+
+impl<'g> From<(Shape<'g>, Outcome)> for Round<'g> {
+    fn from((other, outcome): (Shape<'g>, Outcome)) -> Self {
+        let me = other.play_for_outcome(outcome);
+        Self::new(me, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("input.txt");
+    const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
+
+    #[test]
+    fn test_sample_input() {
+        assert_eq!(part1(&parse(SAMPLE_INPUT).unwrap()), 15);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 10994);
+    }
+
+    #[test]
+    fn test_part2_sample_input() {
+        assert_eq!(part2(&parse(SAMPLE_INPUT).unwrap()), 12);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 12526);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("A Y\nB W\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "W");
+        let error = parse("A Y\nC\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        // found by `cargo fuzz`
+        let error = parse("B\u{7e6}\n").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (2, "\u{7e6}"));
+    }
+
+    #[test]
+    fn test_game() {
+        let game = Game::rock_paper_scissors();
+        let shape = |index| Shape::new(&game, index).unwrap();
+        assert!(shape(1).wins_against(shape(0)));
+        assert!(!shape(0).wins_against(shape(1)));
+        assert!(shape(0).wins_against(shape(2)));
+        assert_eq!(shape(2).to_winner(), shape(0));
+        assert_eq!(shape(0).to_loser(), shape(2));
+        assert_eq!(game.total_score(SAMPLE_INPUT, Reading::Shape), Ok(15));
+        assert_eq!(game.total_score(INPUT, Reading::Outcome), Ok(12526));
+
+        let game = Game::parse(include_str!("rpsls.toml")).unwrap();
+        let shapes = (0..5)
+            .map(|i| Shape::new(&game, i).unwrap())
+            .collect::<Vec<_>>();
+        let beats = |winner: &str, loser: &str| {
+            let find = |name| *shapes.iter().find(|s| s.name() == name).unwrap();
+            find(winner).wins_against(find(loser))
+        };
+        for (winner, loser) in [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ] {
+            assert!(beats(winner, loser), "{winner} should beat {loser}");
+            assert!(!beats(loser, winner), "{loser} should lose to {winner}");
+        }
+        // each shape beats half of the others
+        for &shape in &shapes {
+            assert_eq!(shapes.iter().filter(|&&s| shape.wins_against(s)).count(), 2);
+        }
+        // spock (5) against rock: a win; lizard (4) for a draw against D
+        assert_eq!(game.total_score("A W\nD Y\n", Reading::Shape), Ok(11 + 7));
+        // lose against scissors with paper, win against spock with lizard
+        assert_eq!(
+            game.total_score("C X\r\nE Z\r\n", Reading::Outcome),
+            Ok(2 + 10)
+        );
+        let error = game
+            .total_score("A V\nA V\n", Reading::Outcome)
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert!(Shape::new(&game, 5).is_none());
+
+        // letters need not be ASCII
+        let mut game = Game::rock_paper_scissors();
+        game.shapes[0].opponent = 'é';
+        assert_eq!(game.total_score("é Y\n", Reading::Shape), Ok(2 + 6));
+        let error = game.total_score("é Y Z\n", Reading::Shape).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn test_solve_reader() {
+        for input in [SAMPLE_INPUT, INPUT] {
+            let lines = parse(input).unwrap();
+            let expected = (part1(&lines), part2(&lines));
+            assert_eq!(solve_reader(input.as_bytes()).unwrap(), expected);
+        }
+        let Err(ReadError::Parse(error)) = solve_reader("A Y\nB W\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt::{self, Display};
+
+use common::{ParseError, Solution, Source};
+
+use crate::{line_round, Day02, Game, Outcome, Reading, Round, Score, Shape};
+
+/// What a letter of the second column stands for: a shape, by its index
+/// in the game, or an outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Meaning {
+    Shape(usize),
+    Outcome(Outcome),
+}
+
+/// One way of reading the second column of a strategy guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub reading: Reading,
+    /// Each letter of the second column, and what it stands for.
+    pub letters: Vec<(char, Meaning)>,
+    /// The game with its letters given these meanings.
+    game: Game,
+}
+
+/// A round of the guide, read one way: the shapes played, by their index
+/// in the game, how it ended and what it scored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundScore {
+    pub opponent: usize,
+    pub me: usize,
+    pub outcome: Outcome,
+    pub score: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scored {
+    pub interpretation: Interpretation,
+    pub score: i32,
+    pub rounds: Vec<RoundScore>,
+}
+
+/// Every interpretation of a guide, with what it scores.
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub interpretations: Vec<Scored>,
+    best: usize,
+    worst: usize,
+}
+
+impl Interpretation {
+    /// Every way of giving the second column letters of `game` the
+    /// meanings of its shapes, then the meanings of the outcomes. The
+    /// game's own reading comes first in each.
+    pub fn all(game: &Game) -> Vec<Self> {
+        let mut result = vec![];
+        let letters = game.shapes.iter().map(|shape| shape.me).collect::<Vec<_>>();
+        for order in permutations(letters.len()) {
+            let mut remapped = game.clone();
+            for (&letter, &shape) in letters.iter().zip(&order) {
+                remapped.shapes[shape].me = letter;
+            }
+            result.push(Self {
+                reading: Reading::Shape,
+                letters: letters
+                    .iter()
+                    .zip(&order)
+                    .map(|(&letter, &shape)| (letter, Meaning::Shape(shape)))
+                    .collect(),
+                game: remapped,
+            });
+        }
+        let outcomes = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+        let letters = [game.loss.letter, game.draw.letter, game.win.letter];
+        for order in permutations(outcomes.len()) {
+            let mut remapped = game.clone();
+            for (&letter, &outcome) in letters.iter().zip(&order) {
+                match outcomes[outcome] {
+                    Outcome::Loss => remapped.loss.letter = letter,
+                    Outcome::Draw => remapped.draw.letter = letter,
+                    Outcome::Win => remapped.win.letter = letter,
+                }
+            }
+            result.push(Self {
+                reading: Reading::Outcome,
+                letters: letters
+                    .iter()
+                    .zip(&order)
+                    .map(|(&letter, &outcome)| (letter, Meaning::Outcome(outcomes[outcome])))
+                    .collect(),
+                game: remapped,
+            });
+        }
+        result
+    }
+
+    /// The rounds of the guide read this way. The lines must be rounds of
+    /// the game whatever the reading.
+    pub fn rounds(&self, lines: &[&str]) -> Vec<RoundScore> {
+        lines
+            .iter()
+            .map(|line| {
+                let round = match self.reading {
+                    Reading::Shape => line_round::<Shape, Shape, Round>(&self.game, line),
+                    Reading::Outcome => line_round::<Shape, Outcome, Round>(&self.game, line),
+                };
+                let round = round.expect("rounds are checked by parse");
+                RoundScore {
+                    opponent: round.other.index(),
+                    me: round.me.index(),
+                    outcome: round.outcome(),
+                    score: round.score(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, &(letter, meaning)) in self.letters.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match meaning {
+                Meaning::Shape(shape) => write!(f, "{letter}={}", self.game.shapes[shape].name)?,
+                Meaning::Outcome(outcome) => write!(f, "{letter}={outcome:?}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Search {
+    /// The first interpretation scoring the most.
+    pub fn best(&self) -> &Scored {
+        &self.interpretations[self.best]
+    }
+
+    /// The first interpretation scoring the least.
+    pub fn worst(&self) -> &Scored {
+        &self.interpretations[self.worst]
+    }
+}
+
+impl Display for Search {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, scored) in self.interpretations.iter().enumerate() {
+            let reading = format!("{:?}", scored.interpretation.reading);
+            let interpretation = scored.interpretation.to_string();
+            write!(f, "{reading:<8} {interpretation:<30} {:>8}", scored.score)?;
+            match (i == self.best, i == self.worst) {
+                (true, true) => writeln!(f, "  best, worst")?,
+                (true, false) => writeln!(f, "  best")?,
+                (false, true) => writeln!(f, "  worst")?,
+                (false, false) => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Scores the guide in `input` under every interpretation of its
+    /// second column.
+    pub fn search(&self, input: &str) -> Result<Search, ParseError> {
+        let source = Source::new(Day02::DAY, input);
+        let lines = input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| self.check_round(source, line, &[Reading::Shape, Reading::Outcome]))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(search_game(self, &lines))
+    }
+}
+
+/// Scores the parsed guide of the puzzle under every interpretation of its
+/// second column.
+pub fn search(lines: &[&str]) -> Search {
+    search_game(&Game::rock_paper_scissors(), lines)
+}
+
+fn search_game(game: &Game, lines: &[&str]) -> Search {
+    let interpretations = Interpretation::all(game)
+        .into_iter()
+        .map(|interpretation| {
+            let rounds = interpretation.rounds(lines);
+            let score = rounds.iter().map(|round| round.score).sum();
+            Scored {
+                interpretation,
+                score,
+                rounds,
+            }
+        })
+        .collect::<Vec<_>>();
+    let (mut best, mut worst) = (0, 0);
+    for (i, scored) in interpretations.iter().enumerate() {
+        if scored.score > interpretations[best].score {
+            best = i;
+        }
+        if scored.score < interpretations[worst].score {
+            worst = i;
+        }
+    }
+    Search {
+        interpretations,
+        best,
+        worst,
+    }
+}
+
+/// The orders of `0..n`, in lexicographic order.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut result = vec![];
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            result.push(order);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+
+    const INPUT: &str = include_str!("input.txt");
+    const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(permutations(1), [[0]]);
+        assert_eq!(
+            permutations(3),
+            [
+                [0, 1, 2],
+                [0, 2, 1],
+                [1, 0, 2],
+                [1, 2, 0],
+                [2, 0, 1],
+                [2, 1, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let lines = parse(SAMPLE_INPUT).unwrap();
+        let found = search(&lines);
+        assert_eq!(found.interpretations.len(), 12);
+        let puzzle = &found.interpretations[0];
+        assert_eq!(
+            puzzle.interpretation.to_string(),
+            "X=rock Y=paper Z=scissors"
+        );
+        assert_eq!(puzzle.score, part1(&lines));
+        let scores = puzzle.rounds.iter().map(|r| r.score).collect::<Vec<_>>();
+        assert_eq!(scores, [8, 1, 6]);
+        let puzzle = &found.interpretations[6];
+        assert_eq!(puzzle.interpretation.to_string(), "X=Loss Y=Draw Z=Win");
+        assert_eq!(puzzle.score, part2(&lines));
+        let outcomes = puzzle.rounds.iter().map(|r| r.outcome).collect::<Vec<_>>();
+        assert_eq!(outcomes, [Outcome::Draw, Outcome::Loss, Outcome::Win]);
+        assert_eq!(puzzle.rounds[0].me, 0);
+
+        let lines = parse(INPUT).unwrap();
+        let found = search(&lines);
+        let scores = found.interpretations.iter().map(|s| s.score);
+        assert_eq!(found.best().score, scores.clone().max().unwrap());
+        assert_eq!(found.worst().score, scores.min().unwrap());
+        for scored in &found.interpretations {
+            for (round, &line) in scored.rounds.iter().zip(&lines) {
+                // the letter means what the interpretation says
+                let letter = line.chars().nth(2).unwrap();
+                let (_, meaning) = scored
+                    .interpretation
+                    .letters
+                    .iter()
+                    .find(|(l, _)| *l == letter)
+                    .unwrap();
+                match *meaning {
+                    Meaning::Shape(shape) => assert_eq!(round.me, shape),
+                    Meaning::Outcome(outcome) => assert_eq!(round.outcome, outcome),
+                }
+            }
+        }
+        let report = found.to_string();
+        assert_eq!(report.lines().count(), 12);
+        assert_eq!(report.matches("best").count(), 1);
+        assert!(report.lines().next().unwrap().contains("10994"));
+
+        // with no rounds every interpretation scores 0
+        let report = search(&[]).to_string();
+        assert!(report.lines().next().unwrap().ends_with("  best, worst"));
+        assert_eq!(report.matches("best").count(), 1);
+    }
+
+    #[test]
+    fn test_game_search() {
+        let game = Game::parse(include_str!("rpsls.toml")).unwrap();
+        let found = game.search("A X\nE Z\r\n").unwrap();
+        // every order of five shapes, and of three outcomes
+        assert_eq!(found.interpretations.len(), 120 + 6);
+        assert!(game.search("A V\n").is_err());
+    }
+}
@@ -0,0 +1,315 @@
+use std::fmt::{self, Display};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{line_round, Game, Outcome, Reading, Round, Score, Shape};
+
+/// A round of a match as one player saw it: the shapes played, by their
+/// index in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub mine: usize,
+    pub theirs: usize,
+}
+
+/// A way of playing a match, round after round.
+pub trait Strategy {
+    /// The shape to play next, knowing the rounds played so far.
+    fn play<'g>(&mut self, game: &'g Game, history: &[Turn]) -> Shape<'g>;
+}
+
+/// Plays the shapes of a strategy guide in order, starting over when it
+/// runs out.
+#[derive(Debug, Clone)]
+pub struct FixedGuide {
+    shapes: Vec<usize>,
+}
+
+impl FixedGuide {
+    pub fn new(shapes: Vec<usize>) -> Self {
+        assert!(!shapes.is_empty(), "a guide needs at least one shape");
+        Self { shapes }
+    }
+
+    /// The shapes the guide in `lines` says to play, read one way. With
+    /// outcomes, the shape is the one reaching the outcome against the
+    /// shape the guide expects from the opponent. The lines must be rounds
+    /// of the game.
+    pub fn from_guide(game: &Game, lines: &[&str], reading: Reading) -> Self {
+        let shapes = lines
+            .iter()
+            .map(|line| {
+                let round = match reading {
+                    Reading::Shape => line_round::<Shape, Shape, Round>(game, line),
+                    Reading::Outcome => line_round::<Shape, Outcome, Round>(game, line),
+                };
+                round.expect("rounds are checked by parse").me.index()
+            })
+            .collect();
+        Self::new(shapes)
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn play<'g>(&mut self, game: &'g Game, history: &[Turn]) -> Shape<'g> {
+        let index = self.shapes[history.len() % self.shapes.len()];
+        Shape::new(game, index).expect("the guide is for this game")
+    }
+}
+
+/// Plays any shape, from a seeded generator so matches can be replayed.
+#[derive(Debug, Clone)]
+pub struct Random {
+    rng: ChaCha8Rng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn play<'g>(&mut self, game: &'g Game, _: &[Turn]) -> Shape<'g> {
+        let index = self.rng.gen_range(0..game.shapes.len());
+        Shape::new(game, index).unwrap()
+    }
+}
+
+/// Expects the opponent to play the shape they played most so far, the
+/// first in the game on ties, and plays what beats it.
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyPredictor;
+
+impl Strategy for FrequencyPredictor {
+    fn play<'g>(&mut self, game: &'g Game, history: &[Turn]) -> Shape<'g> {
+        let mut counts = vec![0; game.shapes.len()];
+        for turn in history {
+            counts[turn.theirs] += 1;
+        }
+        let predicted = (0..counts.len())
+            .max_by_key(|&index| (counts[index], std::cmp::Reverse(index)))
+            .unwrap();
+        Shape::new(game, predicted)
+            .unwrap()
+            .play_for_outcome(Outcome::Win)
+    }
+}
+
+/// Plays what beats the opponent's last shape, and the first shape of the
+/// game to open.
+#[derive(Debug, Clone, Default)]
+pub struct BeatLastMove;
+
+impl Strategy for BeatLastMove {
+    fn play<'g>(&mut self, game: &'g Game, history: &[Turn]) -> Shape<'g> {
+        match history.last() {
+            Some(turn) => Shape::new(game, turn.theirs)
+                .unwrap()
+                .play_for_outcome(Outcome::Win),
+            None => Shape::new(game, 0).unwrap(),
+        }
+    }
+}
+
+/// How a match went for one player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub score: i32,
+}
+
+impl Tally {
+    fn add(&mut self, round: &Round) {
+        match round.outcome() {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+        self.score += round.score();
+    }
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{} ({})",
+            self.wins, self.draws, self.losses, self.score
+        )
+    }
+}
+
+/// Plays `rounds` rounds of `game` between two strategies, and tallies
+/// them for each.
+pub fn play_match(
+    game: &Game,
+    first: &mut dyn Strategy,
+    second: &mut dyn Strategy,
+    rounds: usize,
+) -> (Tally, Tally) {
+    let (mut first_history, mut second_history) = (vec![], vec![]);
+    let (mut first_tally, mut second_tally) = (Tally::default(), Tally::default());
+    for _ in 0..rounds {
+        let one = first.play(game, &first_history);
+        let two = second.play(game, &second_history);
+        first_tally.add(&Round::new(one, two));
+        second_tally.add(&Round::new(two, one));
+        first_history.push(Turn {
+            mine: one.index(),
+            theirs: two.index(),
+        });
+        second_history.push(Turn {
+            mine: two.index(),
+            theirs: one.index(),
+        });
+    }
+    (first_tally, second_tally)
+}
+
+/// A strategy entering a tournament, made afresh for each of its matches.
+pub struct Entrant<'a> {
+    pub name: String,
+    pub make: Box<dyn Fn() -> Box<dyn Strategy> + 'a>,
+}
+
+impl<'a> Entrant<'a> {
+    pub fn new(name: &str, make: impl Fn() -> Box<dyn Strategy> + 'a) -> Self {
+        Self {
+            name: name.to_string(),
+            make: Box::new(make),
+        }
+    }
+}
+
+/// A match of a tournament, between the entrants at `first` and `second`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pairing {
+    pub first: usize,
+    pub second: usize,
+    pub first_tally: Tally,
+    pub second_tally: Tally,
+}
+
+/// The matches between every two entrants of a tournament.
+pub struct Tournament {
+    pub names: Vec<String>,
+    pub pairings: Vec<Pairing>,
+}
+
+impl Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pairing in &self.pairings {
+            let first = &self.names[pairing.first];
+            let second = &self.names[pairing.second];
+            writeln!(
+                f,
+                "{first:<12} {:<16} {second:<12} {}",
+                pairing.first_tally.to_string(),
+                pairing.second_tally
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays a match of `rounds` rounds between every two entrants.
+pub fn tournament(game: &Game, entrants: &[Entrant], rounds: usize) -> Tournament {
+    let mut pairings = vec![];
+    for first in 0..entrants.len() {
+        for second in first + 1..entrants.len() {
+            let (first_tally, second_tally) = play_match(
+                game,
+                (entrants[first].make)().as_mut(),
+                (entrants[second].make)().as_mut(),
+                rounds,
+            );
+            pairings.push(Pairing {
+                first,
+                second,
+                first_tally,
+                second_tally,
+            });
+        }
+    }
+    Tournament {
+        names: entrants.iter().map(|e| e.name.clone()).collect(),
+        pairings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+
+    const SAMPLE_INPUT: &str = include_str!("sample_input.txt");
+
+    #[test]
+    fn test_play_match() {
+        let game = Game::rock_paper_scissors();
+        let lines = parse(SAMPLE_INPUT).unwrap();
+        // the elf plays the first column, whatever we do
+        let elf = lines
+            .iter()
+            .map(|line| game.shape_index(line.chars().next().unwrap(), 0).unwrap())
+            .collect::<Vec<_>>();
+        for (reading, expected) in [
+            (Reading::Shape, part1(&lines)),
+            (Reading::Outcome, part2(&lines)),
+        ] {
+            let (me, _) = play_match(
+                &game,
+                &mut FixedGuide::from_guide(&game, &lines, reading),
+                &mut FixedGuide::new(elf.clone()),
+                lines.len(),
+            );
+            assert_eq!(me.score, expected);
+        }
+
+        let (beat, rock) = play_match(&game, &mut BeatLastMove, &mut FixedGuide::new(vec![0]), 10);
+        assert_eq!((beat.wins, beat.draws, beat.losses), (9, 1, 0));
+        assert_eq!((rock.wins, rock.draws, rock.losses), (0, 1, 9));
+        assert_eq!(beat.score, 1 + 3 + 9 * (2 + 6));
+
+        // scissors twice, then rock: the predictor keeps expecting scissors
+        let (predictor, _) = play_match(
+            &game,
+            &mut FrequencyPredictor,
+            &mut FixedGuide::new(vec![2, 2, 0]),
+            9,
+        );
+        assert_eq!(
+            (predictor.wins, predictor.draws, predictor.losses),
+            (5, 3, 1)
+        );
+    }
+
+    #[test]
+    fn test_tournament() {
+        let game = Game::parse(include_str!("rpsls.toml")).unwrap();
+        let entrants = [
+            Entrant::new("guide", || Box::new(FixedGuide::new(vec![0, 1, 2]))),
+            Entrant::new("random", || Box::new(Random::new(42))),
+            Entrant::new("frequency", || Box::new(FrequencyPredictor)),
+            Entrant::new("beat-last", || Box::new(BeatLastMove)),
+        ];
+        let played = tournament(&game, &entrants, 100);
+        assert_eq!(played.pairings.len(), 6);
+        for pairing in &played.pairings {
+            let (one, two) = (pairing.first_tally, pairing.second_tally);
+            assert_eq!(one.wins, two.losses);
+            assert_eq!(one.draws, two.draws);
+            assert_eq!(one.wins + one.draws + one.losses, 100);
+        }
+        // seeded, so the same every time
+        let again = tournament(&game, &entrants, 100);
+        assert_eq!(played.pairings, again.pairings);
+        assert_eq!(played.to_string().lines().count(), 6);
+    }
+}
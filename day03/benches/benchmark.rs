@@ -0,0 +1,15 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day03::*;
+
+const INPUT: &str = include_str!("../src/input.txt");
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let sacks = parse(INPUT).unwrap();
+    c.bench_function("part1", |b| b.iter(|| part1(&sacks)));
+    c.bench_function("part1_scan", |b| b.iter(|| part1_scan(&sacks)));
+    c.bench_function("part2", |b| b.iter(|| part2(&sacks)));
+    c.bench_function("part2_scan", |b| b.iter(|| part2_scan(&sacks)));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use crate::ItemSet;
+
+/// Consecutive rucksacks of a group of elves, and the item types they all
+/// carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group {
+    /// The index of the first rucksack of the group.
+    pub first: usize,
+    pub size: usize,
+    pub common: ItemSet,
+}
+
+/// The rucksacks left over after the last full group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncompleteGroup {
+    /// The index of the first rucksack left over.
+    pub first: usize,
+    pub found: usize,
+    pub size: usize,
+}
+
+impl Group {
+    /// The badge of the group: its only common item, if it has just one.
+    pub fn badge(&self) -> Option<u8> {
+        let mut items = self.common.items();
+        items.next().filter(|_| items.next().is_none())
+    }
+}
+
+impl Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.first + self.size;
+        write!(f, "rucksacks {}-{last}: ", self.first + 1)?;
+        match self.common.len() {
+            0 => return write!(f, "no badge"),
+            1 => write!(f, "badge")?,
+            _ => write!(f, "several badges")?,
+        }
+        for item in self.common.items() {
+            write!(f, " {} ({})", item as char, crate::char_to_priority(item))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for IncompleteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected groups of {} rucksacks, the last one has {}",
+            self.size, self.found
+        )
+    }
+}
+
+impl Error for IncompleteGroup {}
+
+/// Splits the rucksacks into groups of `size`, which must not be zero.
+pub fn groups(sacks: &[&[u8]], size: usize) -> Result<Vec<Group>, IncompleteGroup> {
+    assert!(size > 0, "groups must have at least one rucksack");
+    sacks
+        .chunks(size)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let first = i * size;
+            if chunk.len() < size {
+                return Err(IncompleteGroup {
+                    first,
+                    found: chunk.len(),
+                    size,
+                });
+            }
+            let common = chunk
+                .iter()
+                .map(|sack| ItemSet::from_items(sack))
+                .reduce(ItemSet::intersection)
+                .unwrap();
+            Ok(Group {
+                first,
+                size,
+                common,
+            })
+        })
+        .collect()
+}
+
+/// A line for each group, with its badges, or the lack of one.
+pub fn badge_report(groups: &[Group]) -> String {
+    groups.iter().map(|group| format!("{group}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SAMPLE_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_groups() {
+        let sacks = parse(SAMPLE_INPUT).unwrap();
+        let threes = groups(&sacks, 3).unwrap();
+        let badges = threes.iter().map(Group::badge).collect::<Vec<_>>();
+        assert_eq!(badges, [Some(b'r'), Some(b'Z')]);
+        assert_eq!(
+            badge_report(&threes),
+            "rucksacks 1-3: badge r (18)\nrucksacks 4-6: badge Z (52)\n"
+        );
+
+        let pairs = groups(&sacks, 2).unwrap();
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[2].first, 4);
+        let ones = groups(&sacks, 1).unwrap();
+        assert!(ones.iter().all(|group| group.badge().is_none()));
+        assert!(ones[0]
+            .to_string()
+            .starts_with("rucksacks 1-1: several badges"));
+        assert_eq!(
+            groups(&sacks[..2], 2).unwrap()[0].to_string(),
+            "rucksacks 1-2: several badges f (6) r (18) s (19) F (32) M (39)"
+        );
+        let error = groups(&sacks, 4).unwrap_err();
+        assert_eq!(
+            error,
+            IncompleteGroup {
+                first: 4,
+                found: 2,
+                size: 4
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected groups of 4 rucksacks, the last one has 2"
+        );
+        let split = [b"ab".as_slice(), b"cd"];
+        assert_eq!(
+            groups(&split, 2).unwrap()[0].to_string(),
+            "rucksacks 1-2: no badge"
+        );
+    }
+}
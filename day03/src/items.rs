@@ -0,0 +1,120 @@
+use std::fmt::{self, Debug};
+use std::ops::{BitAnd, BitOr, Sub};
+
+use crate::{char_to_priority, priority_to_char};
+
+/// A set of item types, one bit per priority: bit 0 is `a`, bit 51 is `Z`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: Self = Self(0);
+
+    /// The types of the items in `items`, which must be letters.
+    pub fn from_items(items: &[u8]) -> Self {
+        items.iter().fold(Self::EMPTY, |set, &item| set.with(item))
+    }
+
+    /// This set with `item` added.
+    pub fn with(self, item: u8) -> Self {
+        Self(self.0 | 1 << (char_to_priority(item) - 1))
+    }
+
+    pub fn contains(self, item: u8) -> bool {
+        self.0 & 1 << (char_to_priority(item) - 1) != 0
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// The items of this set that are not in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// The priorities of the items, in increasing order.
+    pub fn priorities(self) -> impl Iterator<Item = i32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            (bits != 0).then(|| {
+                let bit = bits.trailing_zeros();
+                bits &= bits - 1;
+                bit as i32 + 1
+            })
+        })
+    }
+
+    /// The items, by increasing priority.
+    pub fn items(self) -> impl Iterator<Item = u8> {
+        self.priorities().map(priority_to_char)
+    }
+
+    pub fn priority_sum(self) -> i32 {
+        self.priorities().sum()
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl Sub for ItemSet {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+impl Debug for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.items().map(char::from)).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_set() {
+        let left = ItemSet::from_items(b"vJrwpWtwJgWr");
+        let right = ItemSet::from_items(b"hcsFMMfFFhFp");
+        assert_eq!(left.len(), 8);
+        assert!(left.contains(b'J') && !left.contains(b'h'));
+        assert_eq!((left & right).items().collect::<Vec<_>>(), b"p");
+        assert_eq!((left & right).priority_sum(), 16);
+        assert_eq!((left | right).len(), 8 + 7 - 1);
+        assert_eq!((left - right).len(), 7);
+        assert_eq!(left - left, ItemSet::EMPTY);
+        assert!((left - left).is_empty());
+        let ends = ItemSet::from_items(b"Za");
+        assert_eq!(ends.priorities().collect::<Vec<_>>(), [1, 52]);
+        assert_eq!(format!("{ends:?}"), "{'a', 'Z'}");
+    }
+}
@@ -0,0 +1,221 @@
+use common::{ParseError, Solution, SolveError, Source};
+
+mod groups;
+mod items;
+mod rebalance;
+
+pub use groups::{badge_report, groups, Group, IncompleteGroup};
+pub use items::ItemSet;
+pub use rebalance::{rebalance, total_moves, Compartment, Move, Plan};
+
+/// The number of elves in a group of part 2.
+pub const GROUP_SIZE: usize = 3;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed<'a> = Vec<&'a [u8]>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(sacks: &Vec<&[u8]>, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(sacks))
+    }
+
+    fn part2(sacks: &Vec<&[u8]>, _: &()) -> Result<i32, SolveError> {
+        part2(sacks).map_err(|incomplete| SolveError::new(Self::DAY, 2, incomplete))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    let source = Source::new(Day03::DAY, input);
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            source.check_chars(line, |c| c.is_ascii_alphabetic(), "expected an item letter")?;
+            if line.len() % 2 != 0 {
+                return Err(source.error(line, "compartments must have the same size"));
+            }
+            Ok(line.as_bytes())
+        })
+        .collect()
+}
+
+pub fn part1(sacks: &[&[u8]]) -> i32 {
+    sacks
+        .iter()
+        .map(|sack| {
+            let (comp1, comp2) = split_compartments(sack);
+            (ItemSet::from_items(comp1) & ItemSet::from_items(comp2)).priority_sum()
+        })
+        .sum()
+}
+
+pub fn part2(sacks: &[&[u8]]) -> Result<i32, IncompleteGroup> {
+    let groups = groups(sacks, GROUP_SIZE)?;
+    Ok(groups.iter().map(|group| group.common.priority_sum()).sum())
+}
+
+/// `part1` by scanning the compartments for each other's items.
+pub fn part1_scan(sacks: &[&[u8]]) -> i32 {
+    sacks.iter().copied().map(comp_common_sum).sum()
+}
+
+fn comp_common_sum(sack: &[u8]) -> i32 {
+    let (comp1, comp2) = split_compartments(sack);
+    common_items(comp1, comp2)
+        .into_iter()
+        .map(char_to_priority)
+        .sum()
+}
+
+/// `part2` by scanning the rucksacks for each other's items.
+pub fn part2_scan(sacks: &[&[u8]]) -> Result<i32, IncompleteGroup> {
+    let found = sacks.len() % GROUP_SIZE;
+    if found != 0 {
+        return Err(IncompleteGroup {
+            first: sacks.len() - found,
+            found,
+            size: GROUP_SIZE,
+        });
+    }
+    Ok(sacks.chunks(GROUP_SIZE).fold(0i32, |result, chunk| {
+        let common = chunk[1..]
+            .iter()
+            .fold(common_items(chunk[0], chunk[0]), |common, sack| {
+                common_items(sack, &common)
+            });
+        result + common.into_iter().map(char_to_priority).sum::<i32>()
+    }))
+}
+
+fn common_items(slice1: &[u8], slice2: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(slice1.len());
+    slice1
+        .iter()
+        .filter_map(|&char| slice2.iter().find(|&c| *c == char))
+        .for_each(|c| distinct_push(&mut result, *c));
+    result
+}
+
+fn distinct_push(vec: &mut Vec<u8>, item: u8) {
+    if !vec.contains(&item) {
+        vec.push(item)
+    }
+}
+
+fn char_to_priority(char: u8) -> i32 {
+    if char.is_ascii_lowercase() {
+        (char - b'a' + 1) as i32
+    } else {
+        (char - b'A' + 27) as i32
+    }
+}
+
+fn priority_to_char(priority: i32) -> u8 {
+    if priority <= 26 {
+        b'a' + (priority - 1) as u8
+    } else {
+        b'A' + (priority - 27) as u8
+    }
+}
+
+fn split_compartments(sack: &[u8]) -> (&[u8], &[u8]) {
+    (&sack[0..sack.len() / 2], &sack[sack.len() / 2..sack.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("input.txt");
+    const SAMPLE_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_split() {
+        let rucksack = "vJrwpWtwJgWrhcsFMMfFFhFp".as_bytes();
+        assert_eq!(
+            split_compartments(rucksack),
+            ("vJrwpWtwJgWr".as_bytes(), "hcsFMMfFFhFp".as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_common_items() {
+        let rucksack = "vJrwpWtwJgWrhcsFMMfFFhFp".as_bytes();
+        let (comp1, comp2) = split_compartments(rucksack);
+        assert_eq!(common_items(comp1, comp2), [b'p']);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(SAMPLE_INPUT).unwrap()), 157);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 8139);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(SAMPLE_INPUT).unwrap()), Ok(70));
+        assert_eq!(part2(&parse(INPUT).unwrap()), Ok(2668));
+    }
+
+    #[test]
+    fn test_incomplete_group() {
+        // only part 2 needs whole groups
+        let sacks = Day03::parse("abab\ncdcd\nefef\nghgh\n").unwrap();
+        assert_eq!(Day03::part1(&sacks, &()), Ok(1 + 2 + 3 + 4 + 5 + 6 + 7 + 8));
+        let incomplete = IncompleteGroup {
+            first: 3,
+            found: 1,
+            size: GROUP_SIZE,
+        };
+        assert_eq!(part2(&sacks), Err(incomplete));
+        assert_eq!(part2_scan(&sacks), Err(incomplete));
+        let error = Day03::part2(&sacks, &()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 03, part 2: expected groups of 3 rucksacks, the last one has 1"
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(part1_scan(&parse(INPUT).unwrap()), 8139);
+        assert_eq!(part2_scan(&parse(INPUT).unwrap()), Ok(2668));
+    }
+
+    #[test]
+    fn test_item_sets_agree() {
+        use difftest::proptest::collection::vec;
+        use difftest::proptest::sample::select;
+
+        // Few item types make common items likely.
+        let items = b"abcxyzABCXYZ".to_vec();
+        difftest::check(
+            Day03::DAY,
+            "item_set",
+            500,
+            vec(vec(select(items), 1..8), 3..30),
+            |halves| {
+                let sacks = halves.iter().map(|half| {
+                    let mut sack = half.clone();
+                    sack.extend(half.iter().rev().map(|c| c ^ 0x20));
+                    String::from_utf8(sack).unwrap()
+                });
+                sacks.collect::<Vec<_>>().join("\n")
+            },
+            |input| {
+                let sacks = parse(input).map_err(|e| e.to_string())?;
+                difftest::same(part1_scan(&sacks), part1(&sacks))?;
+                difftest::same(part2_scan(&sacks), part2(&sacks))
+            },
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt::{self, Display};
+
+use crate::{split_compartments, ItemSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compartment {
+    First,
+    Second,
+}
+
+/// Items of one type to move out of a compartment into the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub item: u8,
+    pub count: usize,
+    pub from: Compartment,
+}
+
+/// The moves leaving no item type in both compartments of a rucksack.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+}
+
+impl Plan {
+    /// Plans the fewest moves of single items for `sack`. Each type in both
+    /// compartments leaves the one holding fewer of them, or the second on
+    /// ties; the compartments may end up with different sizes.
+    pub fn new(sack: &[u8]) -> Self {
+        let (first, second) = split_compartments(sack);
+        let count = |compartment: &[u8], item| compartment.iter().filter(|&&c| c == item).count();
+        let shared = ItemSet::from_items(first) & ItemSet::from_items(second);
+        let moves = shared
+            .items()
+            .map(|item| {
+                let (in_first, in_second) = (count(first, item), count(second, item));
+                if in_first < in_second {
+                    Move {
+                        item,
+                        count: in_first,
+                        from: Compartment::First,
+                    }
+                } else {
+                    Move {
+                        item,
+                        count: in_second,
+                        from: Compartment::Second,
+                    }
+                }
+            })
+            .collect();
+        Self { moves }
+    }
+
+    /// The number of items moved.
+    pub fn move_count(&self) -> usize {
+        self.moves.iter().map(|m| m.count).sum()
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.moves.is_empty() {
+            return write!(f, "nothing to move");
+        }
+        for (i, m) in self.moves.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            let to = match m.from {
+                Compartment::First => "second",
+                Compartment::Second => "first",
+            };
+            write!(f, "{} {} to the {to}", m.count, m.item as char)?;
+        }
+        Ok(())
+    }
+}
+
+/// The plan for each rucksack.
+pub fn rebalance(sacks: &[&[u8]]) -> Vec<Plan> {
+    sacks.iter().map(|sack| Plan::new(sack)).collect()
+}
+
+/// The number of items to move to rebalance every rucksack.
+pub fn total_moves(sacks: &[&[u8]]) -> usize {
+    rebalance(sacks).iter().map(Plan::move_count).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SAMPLE_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_plan() {
+        let plan = Plan::new(b"vJrwpWtwJgWrhcsFMMfFFhFp");
+        assert_eq!(
+            plan.moves,
+            [Move {
+                item: b'p',
+                count: 1,
+                from: Compartment::Second
+            }]
+        );
+        assert_eq!(plan.to_string(), "1 p to the first");
+
+        let plan = Plan::new(b"aabBcbbcdd");
+        assert_eq!(plan.move_count(), 1 + 1);
+        assert_eq!(plan.to_string(), "1 b to the second, 1 c to the first");
+        assert_eq!(Plan::new(b"abcd").to_string(), "nothing to move");
+        let sacks = parse(SAMPLE_INPUT).unwrap();
+        for (sack, plan) in sacks.iter().zip(rebalance(&sacks)) {
+            // every type is left in a single compartment
+            let (first, second) = split_compartments(sack);
+            let (mut first, mut second) = (ItemSet::from_items(first), ItemSet::from_items(second));
+            for m in &plan.moves {
+                let (from, to) = match m.from {
+                    Compartment::First => (&mut first, &mut second),
+                    Compartment::Second => (&mut second, &mut first),
+                };
+                *from = *from - ItemSet::EMPTY.with(m.item);
+                *to = to.with(m.item);
+            }
+            assert!((first & second).is_empty());
+        }
+        assert_eq!(total_moves(&sacks), 9);
+    }
+}
@@ -0,0 +1,195 @@
+use std::ops::RangeInclusive;
+
+use crate::{coverage_counts, Pair};
+
+/// An elf of the camp: its pair, and whether it is the first or second of
+/// the pair, 0 or 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Elf {
+    pub pair: usize,
+    pub side: usize,
+}
+
+/// The assignments of the camp, for queries by section.
+///
+/// An interval tree laid out in an array: the assignments sorted by start,
+/// the middle one of each slice being the root of the tree of that slice,
+/// with the furthest end in that tree.
+#[derive(Debug, Clone)]
+pub struct SectionIndex {
+    assignments: Vec<(RangeInclusive<usize>, Elf)>,
+    max_end: Vec<usize>,
+}
+
+impl SectionIndex {
+    /// Indexes the assignments of `pairs`. Empty ones, like `5-4`, cover no
+    /// section and are left out.
+    pub fn new(pairs: &[Pair]) -> Self {
+        let mut assignments = pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(pair, (r1, r2))| {
+                [
+                    (r1.clone(), Elf { pair, side: 0 }),
+                    (r2.clone(), Elf { pair, side: 1 }),
+                ]
+            })
+            .filter(|(range, _)| !range.is_empty())
+            .collect::<Vec<_>>();
+        assignments.sort_unstable_by_key(|(range, elf)| (*range.start(), *elf));
+        let mut max_end = vec![0; assignments.len()];
+        build(&assignments, &mut max_end, 0, assignments.len());
+        Self {
+            assignments,
+            max_end,
+        }
+    }
+
+    /// The elves assigned `section`, in order.
+    pub fn elves_at(&self, section: usize) -> Vec<Elf> {
+        let mut elves = self.overlapping(section..=section);
+        elves.sort_unstable();
+        elves
+    }
+
+    /// The pairs with an elf assigned a section of `range`, in order.
+    pub fn pairs_overlapping(&self, range: RangeInclusive<usize>) -> Vec<usize> {
+        let mut pairs = self
+            .overlapping(range)
+            .into_iter()
+            .map(|elf| elf.pair)
+            .collect::<Vec<_>>();
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// The most elves assigned a single section, and the first sections
+    /// assigned that many, or `None` without assignments.
+    pub fn max_coverage(&self) -> Option<(usize, RangeInclusive<usize>)> {
+        let counts = coverage_counts(self.assignments.iter().map(|(range, _)| range));
+        let max = counts.iter().map(|&(_, count)| count).max()?;
+        counts
+            .into_iter()
+            .find(|&(_, count)| count == max)
+            .map(|(range, count)| (count, range))
+    }
+
+    fn overlapping(&self, range: RangeInclusive<usize>) -> Vec<Elf> {
+        let mut elves = vec![];
+        if !range.is_empty() {
+            self.search(&range, 0, self.assignments.len(), &mut elves);
+        }
+        elves
+    }
+
+    fn search(&self, range: &RangeInclusive<usize>, lo: usize, hi: usize, elves: &mut Vec<Elf>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        // nothing in this tree reaches the range
+        if self.max_end[mid] < *range.start() {
+            return;
+        }
+        self.search(range, lo, mid, elves);
+        let (assignment, elf) = &self.assignments[mid];
+        // the ones after it start later still
+        if assignment.start() > range.end() {
+            return;
+        }
+        if assignment.end() >= range.start() {
+            elves.push(*elf);
+        }
+        self.search(range, mid + 1, hi, elves);
+    }
+}
+
+/// Fills `max_end` for the tree of `assignments[lo..hi]`, and returns its
+/// furthest end.
+fn build(
+    assignments: &[(RangeInclusive<usize>, Elf)],
+    max_end: &mut [usize],
+    lo: usize,
+    hi: usize,
+) -> usize {
+    if lo >= hi {
+        return 0;
+    }
+    let mid = (lo + hi) / 2;
+    let left = build(assignments, max_end, lo, mid);
+    let right = build(assignments, max_end, mid + 1, hi);
+    max_end[mid] = *assignments[mid].0.end().max(&left).max(&right);
+    max_end[mid]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const INPUT: &str = include_str!("input.txt");
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    /// The elves assigned a section of `range`, by a scan of every pair.
+    fn scan(pairs: &[Pair], range: &RangeInclusive<usize>) -> Vec<Elf> {
+        let mut elves = vec![];
+        for (pair, (r1, r2)) in pairs.iter().enumerate() {
+            for (side, r) in [r1, r2].into_iter().enumerate() {
+                if r.start().max(range.start()) <= r.end().min(range.end()) {
+                    elves.push(Elf { pair, side });
+                }
+            }
+        }
+        elves
+    }
+
+    #[test]
+    fn test_sample() {
+        let pairs = parse(TEST_INPUT).unwrap();
+        let index = SectionIndex::new(&pairs);
+        assert_eq!(index.elves_at(9), [Elf { pair: 2, side: 1 }]);
+        assert_eq!(index.pairs_overlapping(1..=2), [0, 1, 3, 5]);
+        assert_eq!(index.max_coverage(), Some((8, 6..=6)));
+        assert_eq!(SectionIndex::new(&[]).max_coverage(), None);
+        assert!(index.elves_at(10).is_empty());
+    }
+
+    #[test]
+    fn test_against_scan() {
+        let pairs = parse(INPUT).unwrap();
+        let index = SectionIndex::new(&pairs);
+        let last = pairs
+            .iter()
+            .map(|(r1, r2)| *r1.end().max(r2.end()))
+            .max()
+            .unwrap();
+        let mut max = 0;
+        for section in 0..=last + 1 {
+            let elves = scan(&pairs, &(section..=section));
+            assert_eq!(index.elves_at(section), elves, "section {section}");
+            max = max.max(elves.len());
+        }
+        for start in (0..=last).step_by(7) {
+            for len in [0, 1, 5, 30] {
+                let range = start..=start + len;
+                let mut expected = scan(&pairs, &range)
+                    .into_iter()
+                    .map(|e| e.pair)
+                    .collect::<Vec<_>>();
+                expected.dedup();
+                assert_eq!(
+                    index.pairs_overlapping(range.clone()),
+                    expected,
+                    "{range:?}"
+                );
+            }
+        }
+        let (count, sections) = index.max_coverage().unwrap();
+        assert_eq!(count, max);
+        let first = (0..=last)
+            .find(|&s| scan(&pairs, &(s..=s)).len() == max)
+            .unwrap();
+        assert_eq!(*sections.start(), first);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::{self, Debug};
+use std::ops::RangeInclusive;
+
+/// A set of sections, kept as sorted ranges that neither overlap nor touch.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<RangeInclusive<usize>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ranges of the set, in order.
+    pub fn ranges(&self) -> &[RangeInclusive<usize>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of sections in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|r| r.end() - r.start() + 1).sum()
+    }
+
+    pub fn contains(&self, section: usize) -> bool {
+        let i = self.ranges.partition_point(|r| *r.end() < section);
+        self.ranges.get(i).is_some_and(|r| r.contains(&section))
+    }
+
+    /// Adds the sections of `range`, merging it with the ranges it overlaps
+    /// or touches.
+    pub fn insert(&mut self, range: RangeInclusive<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = range.into_inner();
+        // the ranges ending right before `start`, or later, and starting
+        // right after `end`, or earlier, merge with it
+        let first = self
+            .ranges
+            .partition_point(|r| r.end().saturating_add(1) < start);
+        let last = self
+            .ranges
+            .partition_point(|r| *r.start() <= end.saturating_add(1));
+        if first < last {
+            start = start.min(*self.ranges[first].start());
+            end = end.max(*self.ranges[last - 1].end());
+        }
+        self.ranges.splice(first..last, [start..=end]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in &other.ranges {
+            result.insert(range.clone());
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            if start <= end {
+                ranges.push(start..=end);
+            }
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The sections of this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+        for range in &self.ranges {
+            // skip the ranges of `other` ending before this one
+            while other.ranges.get(j).is_some_and(|b| b.end() < range.start()) {
+                j += 1;
+            }
+            // the first section not removed yet, if any is left
+            let mut start = Some(*range.start());
+            for b in other.ranges[j..]
+                .iter()
+                .take_while(|b| b.start() <= range.end())
+            {
+                let Some(from) = start else { break };
+                if *b.start() > from {
+                    ranges.push(from..=b.start() - 1);
+                }
+                start = b.end().checked_add(1);
+            }
+            if let Some(from) = start.filter(|from| from <= range.end()) {
+                ranges.push(from..=*range.end());
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The sections of `within` not in the set.
+    pub fn complement(&self, within: RangeInclusive<usize>) -> Self {
+        Self::from_iter([within]).difference(self)
+    }
+
+    /// The sections missing between the first and the last of the set.
+    pub fn gaps(&self) -> Self {
+        let ranges = self
+            .ranges
+            .windows(2)
+            .map(|pair| pair[0].end() + 1..=pair[1].start() - 1)
+            .collect();
+        Self { ranges }
+    }
+}
+
+impl FromIterator<RangeInclusive<usize>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<usize>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl Debug for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+/// Splits the sections covered by `ranges` into runs covered by the same
+/// number of them, in order, with that number.
+pub fn coverage_counts<'a>(
+    ranges: impl IntoIterator<Item = &'a RangeInclusive<usize>>,
+) -> Vec<(RangeInclusive<usize>, usize)> {
+    // +1 where a range starts, -1 right after it ends
+    let mut events = vec![];
+    for range in ranges.into_iter().filter(|r| !r.is_empty()) {
+        events.push((*range.start(), 1));
+        if let Some(after) = range.end().checked_add(1) {
+            events.push((after, -1));
+        }
+    }
+    events.sort_unstable();
+    let mut result = vec![];
+    let mut count = 0isize;
+    for (i, &(section, delta)) in events.iter().enumerate() {
+        count += delta;
+        let next = events.get(i + 1).map(|&(s, _)| s);
+        if next == Some(section) {
+            continue;
+        }
+        if count > 0 {
+            let end = next.map_or(usize::MAX, |next| next - 1);
+            result.push((section..=end, count as usize));
+        }
+    }
+    result
+}
+
+/// The sections covered by a number of `ranges` that is `wanted`.
+pub fn covered_by<'a>(
+    ranges: impl IntoIterator<Item = &'a RangeInclusive<usize>>,
+    wanted: impl Fn(usize) -> bool,
+) -> IntervalSet {
+    coverage_counts(ranges)
+        .into_iter()
+        .filter(|(_, count)| wanted(*count))
+        .map(|(range, _)| range)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<usize>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[5..=7, 1..=2]);
+        assert_eq!(s.ranges(), [1..=2, 5..=7]);
+        s.insert(3..=3);
+        assert_eq!(s.ranges(), [1..=3, 5..=7]);
+        s.insert(4..=4);
+        assert_eq!(s.ranges(), [1..=7]);
+        // like an assignment "10-9"
+        s.insert(RangeInclusive::new(10, 9));
+        assert_eq!(s.ranges(), [1..=7]);
+        s.insert(0..=20);
+        assert_eq!(s.ranges(), [0..=20]);
+        assert_eq!(s.len(), 21);
+        assert!(s.contains(20) && !s.contains(21));
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[1..=4, 8..=12, 20..=20]);
+        let b = set(&[3..=9, 11..=11, 15..=25]);
+        assert_eq!(a.union(&b).ranges(), [1..=12, 15..=25]);
+        assert_eq!(
+            a.intersection(&b).ranges(),
+            [3..=4, 8..=9, 11..=11, 20..=20]
+        );
+        assert_eq!(a.difference(&b).ranges(), [1..=2, 10..=10, 12..=12]);
+        assert_eq!(b.difference(&a).ranges(), [5..=7, 15..=19, 21..=25]);
+        assert_eq!(a.gaps().ranges(), [5..=7, 13..=19]);
+        assert_eq!(a.complement(0..=10).ranges(), [0..=0, 5..=7]);
+        assert!(a.difference(&a).is_empty());
+
+        // against sets of sections
+        let sections = |s: &IntervalSet| (0..30).filter(|&x| s.contains(x)).collect::<Vec<_>>();
+        let (a_in, b_in) = (sections(&a), sections(&b));
+        let both = a_in.iter().filter(|x| b_in.contains(x)).count();
+        assert_eq!(a.intersection(&b).len(), both);
+        assert_eq!(a.union(&b).len(), a_in.len() + b_in.len() - both);
+        assert_eq!(a.difference(&b).len(), a_in.len() - both);
+    }
+
+    #[test]
+    fn test_coverage_counts() {
+        let ranges = [2..=4, 3..=6, 4..=4, 9..=10];
+        assert_eq!(
+            coverage_counts(&ranges),
+            [(2..=2, 1), (3..=3, 2), (4..=4, 3), (5..=6, 1), (9..=10, 1)]
+        );
+        assert_eq!(
+            covered_by(&ranges, |n| n == 1).ranges(),
+            [2..=2, 5..=6, 9..=10]
+        );
+        assert_eq!(covered_by(&ranges, |n| n > 1).ranges(), [3..=4]);
+        assert_eq!(coverage_counts(&[0..=usize::MAX]), [(0..=usize::MAX, 1)]);
+    }
+}
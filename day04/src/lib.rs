@@ -0,0 +1,171 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use common::{for_each_line, ParseError, ReadError, Solution, SolveError, Source};
+
+mod index;
+mod intervals;
+
+pub use index::{Elf, SectionIndex};
+pub use intervals::{coverage_counts, covered_by, IntervalSet};
+
+pub type Pair = (RangeInclusive<usize>, RangeInclusive<usize>);
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Parsed<'a> = Vec<Pair>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Pair>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(pairs: &Vec<Pair>, _: &()) -> Result<usize, SolveError> {
+        Ok(part1(pairs))
+    }
+
+    fn part2(pairs: &Vec<Pair>, _: &()) -> Result<usize, SolveError> {
+        Ok(part2(pairs))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Pair>, ParseError> {
+    let source = Source::new(Day04::DAY, input);
+    input
+        .trim()
+        .lines()
+        .map(|line| parse_pair(source, line))
+        .collect()
+}
+
+fn parse_pair(source: Source, line: &str) -> Result<Pair, ParseError> {
+    let (left, right) = source.split_once(line, ",")?;
+    Ok((make_range(source, left)?, make_range(source, right)?))
+}
+
+pub fn part1(pairs: &[Pair]) -> usize {
+    solve(pairs, contains)
+}
+
+pub fn part2(pairs: &[Pair]) -> usize {
+    solve(pairs, overlaps)
+}
+
+fn solve(
+    pairs: &[Pair],
+    filter: impl Fn(&RangeInclusive<usize>, &RangeInclusive<usize>) -> bool,
+) -> usize {
+    pairs.iter().filter(|(r1, r2)| filter(r1, r2)).count()
+}
+
+/// Both parts, reading the input a line at a time instead of holding it.
+/// Blank lines are skipped.
+pub fn solve_reader(reader: impl BufRead) -> Result<(usize, usize), ReadError> {
+    let (mut part1, mut part2) = (0, 0);
+    for_each_line(Day04::DAY, reader, |source, line| {
+        if !line.is_empty() {
+            let (r1, r2) = parse_pair(source, line)?;
+            part1 += contains(&r1, &r2) as usize;
+            part2 += overlaps(&r1, &r2) as usize;
+        }
+        Ok(())
+    })?;
+    Ok((part1, part2))
+}
+
+/// Every elf's assignment, two per pair.
+pub fn assignments(pairs: &[Pair]) -> impl Iterator<Item = &RangeInclusive<usize>> {
+    pairs.iter().flat_map(|(r1, r2)| [r1, r2])
+}
+
+/// The sections some elf is assigned.
+pub fn camp_coverage(pairs: &[Pair]) -> IntervalSet {
+    assignments(pairs).cloned().collect()
+}
+
+fn make_range(source: Source, txt: &str) -> Result<RangeInclusive<usize>, ParseError> {
+    let (start, end) = source.split_once(txt, "-")?;
+    Ok(source.parse(start)?..=source.parse(end)?)
+}
+
+fn contains(r1: &RangeInclusive<usize>, r2: &RangeInclusive<usize>) -> bool {
+    (r1.start() <= r2.start() && r1.end() >= r2.end())
+        || (r2.start() <= r1.start() && r2.end() >= r1.end())
+}
+
+fn overlaps(r1: &RangeInclusive<usize>, r2: &RangeInclusive<usize>) -> bool {
+    (r1.start() <= r2.start() && r1.end() >= r2.start())
+        || (r2.start() <= r1.start() && r2.end() >= r1.start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("input.txt");
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 2);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 441);
+    }
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 4);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 861);
+    }
+
+    #[test]
+    fn test_camp_coverage() {
+        let pairs = parse(INPUT).unwrap();
+        let covered = camp_coverage(&pairs);
+        let sections = 0..=*covered.ranges().last().unwrap().end() + 1;
+        let counts = sections
+            .clone()
+            .map(|s| assignments(&pairs).filter(|r| r.contains(&s)).count())
+            .collect::<Vec<_>>();
+        let expected = |wanted: fn(usize) -> bool| {
+            sections
+                .clone()
+                .filter(|&s| wanted(counts[s]))
+                .map(|s| s..=s)
+                .collect::<IntervalSet>()
+        };
+        assert_eq!(covered, expected(|n| n > 0));
+        assert_eq!(covered.complement(sections.clone()), expected(|n| n == 0));
+        assert_eq!(
+            covered_by(assignments(&pairs), |n| n == 1),
+            expected(|n| n == 1)
+        );
+        assert_eq!(
+            covered_by(assignments(&pairs), |n| n > 3),
+            expected(|n| n > 3)
+        );
+
+        let pairs = parse(TEST_INPUT).unwrap();
+        assert_eq!(camp_coverage(&pairs).ranges(), [2..=9]);
+        assert!(camp_coverage(&pairs).gaps().is_empty());
+        assert_eq!(
+            covered_by(assignments(&pairs), |n| n == 1).ranges(),
+            [9..=9]
+        );
+    }
+
+    #[test]
+    fn test_solve_reader() {
+        for input in [TEST_INPUT, INPUT] {
+            let pairs = parse(input).unwrap();
+            let expected = (part1(&pairs), part2(&pairs));
+            assert_eq!(solve_reader(input.as_bytes()).unwrap(), expected);
+        }
+        let Err(ReadError::Parse(error)) = solve_reader("2-4,6-8\n2-3,4_5\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (2, 5));
+    }
+}
@@ -0,0 +1,267 @@
+use common::{ParseError, Solution, SolveError, Source};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed<'a> = Input;
+    type Params = ();
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &()) -> Result<String, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Input, _: &()) -> Result<String, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+pub fn part1(input: &Input) -> String {
+    solve::<Model9000>(input)
+}
+
+pub fn part2(input: &Input) -> String {
+    solve::<Model9001>(input)
+}
+
+fn solve<C: Crane>(input: &Input) -> String {
+    let mut input = input.clone();
+    exec_moves::<C>(&mut input);
+    collect_top_crates(&input)
+}
+
+fn exec_moves<C: Crane>(input: &mut Input) {
+    for m in input.moves.iter() {
+        let stacks = &mut input.stacks;
+        let mut from_stack = std::mem::take(&mut stacks[m.from - 1]);
+        let mut to_stack = std::mem::take(&mut stacks[m.to - 1]);
+        C::apply_move(m.qty, &mut from_stack, &mut to_stack);
+        stacks[m.from - 1] = from_stack;
+        stacks[m.to - 1] = to_stack;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    stacks: Stacks,
+    moves: Vec<Move>,
+}
+
+type Stacks = Vec<Stack>;
+type Stack = Vec<u8>;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Move {
+    from: usize,
+    to: usize,
+    qty: u8,
+}
+
+trait Crane {
+    fn apply_move(crate_count: u8, from_stack: &mut Stack, to_stack: &mut Stack);
+}
+
+struct Model9000;
+
+impl Crane for Model9000 {
+    fn apply_move(crate_count: u8, from_stack: &mut Stack, to_stack: &mut Stack) {
+        for _ in 0..crate_count {
+            to_stack.push(from_stack.pop().unwrap())
+        }
+    }
+}
+
+struct Model9001;
+
+impl Crane for Model9001 {
+    fn apply_move(crate_count: u8, from_stack: &mut Stack, to_stack: &mut Stack) {
+        let from_index = from_stack.len() - crate_count as usize;
+        to_stack.extend_from_slice(&from_stack[from_index..]);
+        from_stack.truncate(from_index);
+    }
+}
+
+fn collect_top_crates(input: &Input) -> String {
+    // Safety: I built the damn thing myself ! (everything an ascii char)
+    // I'd still unwrap it anyways
+    unsafe {
+        String::from_utf8_unchecked(
+            input
+                .stacks
+                .iter()
+                .filter_map(|stack| stack.last())
+                .copied()
+                .collect::<Vec<u8>>(),
+        )
+    }
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let source = Source::new(Day05::DAY, input);
+    let (stacks, rest) = parse_stacks(source, input)?;
+    let heights = stacks.iter().map(Vec::len).collect();
+    let moves = parse_moves(source, heights, rest)?;
+    Ok(Input { stacks, moves })
+}
+
+fn parse_stacks<'a>(source: Source, mut input: &'a str) -> Result<(Stacks, &'a str), ParseError> {
+    let mut stacks: Vec<Vec<u8>> = vec![];
+    loop {
+        let (line, rest) = input
+            .split_once('\n')
+            .ok_or_else(|| source.eof("expected a blank line after the stacks"))?;
+        input = rest;
+        if line.trim().is_empty() {
+            for stack in stacks.iter_mut() {
+                stack.reverse()
+            }
+            return Ok((stacks, rest));
+        } else {
+            let char = *line
+                .as_bytes()
+                .get(1)
+                .ok_or_else(|| source.error(line, "expected a row of crates"))?;
+            if (b'1'..=b'9').contains(&char) {
+                continue;
+            } else {
+                for i in 0.. {
+                    if let Some(&name) = line.as_bytes().get(i * 4 + 1) {
+                        if name.is_ascii_uppercase() {
+                            if stacks.len() <= i {
+                                stacks.resize_with(i + 1, Default::default);
+                            }
+                            stacks[i].push(name);
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Also follows the height of each stack, starting from `heights`, to
+/// check no move takes more crates than its stack holds.
+fn parse_moves(
+    source: Source,
+    mut heights: Vec<usize>,
+    input: &str,
+) -> Result<Vec<Move>, ParseError> {
+    let stack_count = heights.len();
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let rest = source.strip_prefix(line, "move ")?;
+            let (qty_text, rest) = source.split_once(rest, " from ")?;
+            let (from, to) = source.split_once(rest, " to ")?;
+            let m = Move {
+                qty: source.parse(qty_text)?,
+                from: parse_stack_number(source, stack_count, from)?,
+                to: parse_stack_number(source, stack_count, to)?,
+            };
+            let height = heights[m.from - 1];
+            let qty = m.qty as usize;
+            if qty > height {
+                let reason = format!("stack {} only has {height} crates", m.from);
+                return Err(source.error(qty_text, reason));
+            }
+            heights[m.from - 1] -= qty;
+            heights[m.to - 1] += qty;
+            Ok(m)
+        })
+        .collect()
+}
+
+fn parse_stack_number(source: Source, stack_count: usize, txt: &str) -> Result<usize, ParseError> {
+    let number = source.parse(txt)?;
+    if (1..=stack_count).contains(&number) {
+        Ok(number)
+    } else {
+        Err(source.error(txt, format!("expected a stack from 1 to {stack_count}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("input.txt");
+    const TEST_INPUT: &str = include_str!("sample_input.txt");
+
+    #[test]
+    fn test_parsing() {
+        let input = parse(TEST_INPUT).unwrap();
+        assert_eq!(input.stacks.len(), 3);
+        assert_eq!(input.stacks[0], [b'Z', b'N']);
+        assert_eq!(input.stacks[1], [b'M', b'C', b'D']);
+        assert_eq!(input.stacks[2], [b'P']);
+        assert_eq!(
+            input.moves,
+            [
+                Move {
+                    qty: 1,
+                    from: 2,
+                    to: 1
+                },
+                Move {
+                    qty: 3,
+                    from: 1,
+                    to: 3
+                },
+                Move {
+                    qty: 2,
+                    from: 2,
+                    to: 1
+                },
+                Move {
+                    qty: 1,
+                    from: 1,
+                    to: 2
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), "CMZ");
+        assert_eq!(part1(&parse(INPUT).unwrap()), "CVCWCRTVQ");
+        // an emptied stack has no top crate
+        let input = parse("    [B]\n[A] [C]\n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+        assert_eq!(part1(&input), "A");
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), "MCD");
+        assert_eq!(part2(&parse(INPUT).unwrap()), "CNSCZWLVT");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_INPUT.replace("move 3 from 1 to 3", "move 3 form 1 to 3");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (5, 7, 6));
+        assert_eq!(error.text, "3 form 1 to 3");
+        let input = TEST_INPUT.replace("from 2 to 1", "from 2 to 4");
+        let error = parse(&input).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (6, 18, "4")
+        );
+        assert!(parse("    [D]\n").is_err());
+        // the second move empties stack 1
+        let input = TEST_INPUT.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (7, 6, "4"));
+        assert!(error.to_string().contains("stack 1 only has 3 crates"));
+    }
+}
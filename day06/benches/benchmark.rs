@@ -1,6 +1,8 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use day06::*;
 
+const INPUT: &str = include_str!("../src/input.txt");
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("solve 4", |b| b.iter(|| solve::<PACKET_MARKER_SIZE>(INPUT)));
     c.bench_function("solve 14", |b| {
@@ -1,7 +1,44 @@
-pub const INPUT: &str = include_str!("input.txt");
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use common::{ParseError, Solution, SolveError, Source};
+
 pub const PACKET_MARKER_SIZE: usize = 4;
 pub const MESSAGE_MARKER_SIZE: usize = 14;
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Parsed<'a> = &'a str;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &&str, _: &()) -> Result<usize, SolveError> {
+        Ok(solve_faster::<PACKET_MARKER_SIZE>(input))
+    }
+
+    fn part2(input: &&str, _: &()) -> Result<usize, SolveError> {
+        Ok(solve_faster::<MESSAGE_MARKER_SIZE>(input))
+    }
+}
+
+/// The datastream, checked to be a single line of letters.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    let source = Source::new(Day06::DAY, input);
+    let stream = input.trim();
+    source.check_chars(
+        stream,
+        |c| c.is_ascii_lowercase(),
+        "expected a lowercase letter",
+    )?;
+    Ok(stream)
+}
+
 pub fn solve<const WINDOW_SIZE: usize>(input: &str) -> usize {
     let (window_index, _) = input
         .as_bytes()
@@ -63,14 +100,20 @@ impl<const WINDOW_SIZE: usize> Slider<WINDOW_SIZE> {
             self.duplicate_count += 1;
         }
         self.index += 1;
-        self.duplicate_count == 0 && self.index > WINDOW_SIZE
+        self.duplicate_count == 0 && self.index >= WINDOW_SIZE
     }
 }
 
 #[cfg(test)]
 mod tests {
+    extern crate alloc;
+
+    use alloc::string::{String, ToString};
+
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
+
     #[test]
     fn test_is_start_marker() {
         assert!(is_start_marker("abcd".as_bytes()));
@@ -87,9 +130,10 @@ mod tests {
             "nppdvjthqldpwncqszvftbrmjlhg",
             "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
             "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+            "abcdaa",
             INPUT,
         ];
-        let results = [7, 5, 6, 10, 11, 1578];
+        let results = [7, 5, 6, 10, 11, 4, 1578];
         strings
             .iter()
             .zip(results.iter())
@@ -105,4 +149,24 @@ mod tests {
         assert_eq!(solve::<14>(INPUT), 2178);
         assert_eq!(solve_faster::<14>(INPUT), 2178);
     }
+
+    #[test]
+    fn test_solve_faster_agrees() {
+        use difftest::proptest::collection::vec;
+
+        // Few letters make duplicates likely, and a message marker at the
+        // end makes sure there is one of each kind.
+        difftest::check(
+            Day06::DAY,
+            "solve_faster",
+            1000,
+            vec(b'a'..b'e', 0..200),
+            |letters| String::from_utf8(letters.clone()).unwrap() + "fghijklmnopqrs\n",
+            |input| {
+                let stream = parse(input).map_err(|e| e.to_string())?;
+                difftest::same(solve::<4>(stream), solve_faster::<4>(stream))?;
+                difftest::same(solve::<14>(stream), solve_faster::<14>(stream))
+            },
+        );
+    }
 }
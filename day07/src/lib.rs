@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-pub const INPUT: &str = include_str!("input.txt");
+use common::{ParseError, Solution, SolveError, Source};
+
 const MAX_USAGE: u64 = 70000000 - 30000000;
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -71,80 +72,104 @@ struct Shell<'a> {
     fs: FileSystem<'a>,
     pwd: Path<'a>,
     dir_handle: Option<DirHandle>,
+    /// The size of every file listed so far, bounding the directory sizes.
+    listed_size: u64,
 }
 
 impl<'a> Shell<'a> {
-    fn run_session<'b>(&'b mut self, input: &'a str) {
+    fn run_session(&mut self, source: Source<'a>) -> Result<(), ParseError> {
         enum State {
             Cmd,
             Dir(u64),
         }
         use State::*;
         let mut state = Cmd;
-        for line in input.lines().filter(|line| !line.is_empty()) {
-            let line = line.as_bytes();
+        for line in source.text().lines().filter(|line| !line.is_empty()) {
             match state {
-                Cmd => match line[0] {
-                    b'$' => self.process_command(&line[2..]),
-                    _ => state = Dir(self.process_node(line)),
+                Cmd => match line.strip_prefix("$ ") {
+                    Some(command) => self.process_command(source, command)?,
+                    None => state = Dir(self.process_node(source, line)?),
                 },
-                Dir(size) => match line[0] {
-                    b'$' => {
+                Dir(size) => match line.strip_prefix("$ ") {
+                    Some(command) => {
                         self.fs.add_size(self.dir_handle, size);
-                        self.process_command(&line[2..]);
+                        self.process_command(source, command)?;
                         state = Cmd;
                     }
-                    _ => state = Dir(size + self.process_node(line)),
+                    None => state = Dir(size + self.process_node(source, line)?),
                 },
             }
         }
         if let Dir(size) = state {
             self.fs.add_size(self.dir_handle, size);
         }
+        Ok(())
     }
 
-    fn process_command<'b>(&'b mut self, line: &'a [u8]) {
-        if &line[0..2] == b"cd" {
-            let rest = &line[3..];
-            match rest {
-                b"\\" => {
-                    self.pwd = Path::default();
-                    let handle = self.fs.dir_handle(&self.pwd.0);
-                    self.dir_handle = Some(handle);
-                }
-                b".." => {
-                    self.pwd.up();
-                    let handle = self.fs.dir_handle(&self.pwd.0);
-                    self.dir_handle = Some(handle);
-                }
-                bytes => {
-                    self.pwd
-                        .down(unsafe { std::str::from_utf8_unchecked(bytes) });
-                    let child = self.fs.dir_handle(self.pwd.as_slice());
-                    self.fs.dir_mut(child).parent = self.dir_handle;
-                    self.dir_handle = Some(child);
-                }
+    fn process_command(&mut self, source: Source, command: &'a str) -> Result<(), ParseError> {
+        match command.split_once(' ') {
+            Some(("cd", "\\")) => {
+                self.pwd = Path::default();
+                let handle = self.fs.dir_handle(&self.pwd.0);
+                self.dir_handle = Some(handle);
+            }
+            Some(("cd", "..")) => {
+                self.pwd.up();
+                let handle = self.fs.dir_handle(&self.pwd.0);
+                self.dir_handle = Some(handle);
             }
+            Some(("cd", name)) => {
+                self.pwd.down(name);
+                let child = self.fs.dir_handle(self.pwd.as_slice());
+                self.fs.dir_mut(child).parent = self.dir_handle;
+                self.dir_handle = Some(child);
+            }
+            None if command == "ls" => {}
+            _ => return Err(source.error(command, "expected a cd or ls command")),
         }
+        Ok(())
     }
 
-    fn process_node<'b>(&'b mut self, line: &'a [u8]) -> u64 {
-        let first = unsafe { std::str::from_utf8_unchecked(line) }
-            .split_whitespace()
-            .next()
-            .unwrap();
+    fn process_node(&mut self, source: Source, line: &'a str) -> Result<u64, ParseError> {
+        let (first, _) = source.split_once(line, " ")?;
         if first == "dir" {
-            0
-        } else {
-            first.parse::<u64>().unwrap()
+            return Ok(0);
         }
+        let size = source.parse(first)?;
+        self.listed_size = self
+            .listed_size
+            .checked_add(size)
+            .ok_or_else(|| source.error(first, "files too large in total"))?;
+        Ok(size)
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Parsed<'a> = FileSystem<'a>;
+    type Params = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<FileSystem<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(fs: &FileSystem, _: &()) -> Result<u64, SolveError> {
+        Ok(part1(fs))
+    }
+
+    fn part2(fs: &FileSystem, _: &()) -> Result<u64, SolveError> {
+        Ok(part2(fs))
     }
 }
 
-pub fn parse(input: &str) -> FileSystem {
+pub fn parse(input: &str) -> Result<FileSystem<'_>, ParseError> {
     let mut shell = Shell::default();
-    shell.run_session(input);
-    shell.fs
+    shell.run_session(Source::new(Day07::DAY, input))?;
+    Ok(shell.fs)
 }
 
 pub fn part1(fs: &FileSystem) -> u64 {
@@ -170,28 +195,36 @@ pub fn part2(fs: &FileSystem) -> u64 {
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     pub const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
     fn test_shell() {
         let mut shell = Shell::default();
-        shell.run_session(TEST_INPUT);
+        shell.run_session(Source::new(7, TEST_INPUT)).unwrap();
         assert_eq!(shell.fs.directories[0].total_size, 48381165);
     }
 
     #[test]
     fn test_part1() {
-        let fs = parse(TEST_INPUT);
+        let fs = parse(TEST_INPUT).unwrap();
         assert_eq!(part1(&fs), 95437);
-        let fs = parse(INPUT);
+        let fs = parse(INPUT).unwrap();
         assert_eq!(part1(&fs), 2104783);
     }
 
     #[test]
     fn test_part2() {
-        let fs = parse(TEST_INPUT);
+        let fs = parse(TEST_INPUT).unwrap();
         assert_eq!(part2(&fs), 24933642);
-        let fs = parse(INPUT);
+        let fs = parse(INPUT).unwrap();
         assert_eq!(part2(&fs), 5883165);
     }
+
+    #[test]
+    fn test_parse_error() {
+        // found by `cargo fuzz`
+        let error = parse("$ ls\n18446744073709551615 a\n1 b\n").err().unwrap();
+        assert_eq!((error.line, error.text.as_str()), (3, "1"));
+    }
 }
@@ -1,70 +1,168 @@
-pub const INPUT: &str = include_str!("input.txt");
+#![cfg_attr(not(feature = "std"), no_std)]
 
-type Pos = (i32, i32);
+extern crate alloc;
 
-pub fn part1(input: &str) -> u64 {
-    solve::<2>(input)
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use common::{for_each_line, ReadError};
+use common::{ParseError, Solution, SolveError, Source, Vec2};
+
+#[cfg(feature = "std")]
+mod visualize;
+
+#[cfg(feature = "std")]
+pub use visualize::visualize;
+
+type Pos = Vec2<i32>;
+pub type Motion = (Pos, usize);
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Parsed<'a> = Vec<Motion>;
+    type Params = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(motions: &Vec<Motion>, _: &()) -> Result<u64, SolveError> {
+        Ok(part1(motions))
+    }
+
+    fn part2(motions: &Vec<Motion>, _: &()) -> Result<u64, SolveError> {
+        Ok(part2(motions))
+    }
 }
 
-pub fn part2(input: &str) -> u64 {
-    solve::<10>(input)
+pub fn part1(motions: &[Motion]) -> u64 {
+    solve::<2>(motions)
 }
 
-#[inline(always)]
-fn set_bit(pos: Pos, slice: &mut [usize]) {
-    let index = ((pos.0 + 512) + (pos.1 + 512) * 1024) as usize;
-    let word = &mut slice[index >> 6];
-    let shift = index & 0b111111;
-    *word |= 1 << shift;
+pub fn part2(motions: &[Motion]) -> u64 {
+    solve::<10>(motions)
 }
 
-pub fn solve<const COUNT: usize>(input: &str) -> u64 {
+/// The positions a knot visited: a bit set around the start, where ropes
+/// spend their time, and an ordered set for any further.
+struct Visited {
+    near: Box<[u64]>,
+    far: BTreeSet<Pos>,
+}
+
+impl Visited {
+    const HALF_SIDE: i32 = 512;
+
+    fn new() -> Self {
+        let side = 2 * Self::HALF_SIDE as usize;
+        let mut result = Self {
+            near: vec![0; side * side / 64].into_boxed_slice(),
+            far: BTreeSet::new(),
+        };
+        result.insert(Pos::ZERO);
+        result
+    }
+
+    #[inline(always)]
+    fn insert(&mut self, pos: Pos) {
+        let range = -Self::HALF_SIDE..Self::HALF_SIDE;
+        if range.contains(&pos.x) && range.contains(&pos.y) {
+            let side = 2 * Self::HALF_SIDE;
+            let index = ((pos.x + Self::HALF_SIDE) + (pos.y + Self::HALF_SIDE) * side) as usize;
+            let word = &mut self.near[index >> 6];
+            let shift = index & 0b111111;
+            *word |= 1 << shift;
+        } else {
+            self.far.insert(pos);
+        }
+    }
+
+    fn count(&self) -> u64 {
+        self.near.iter().map(|u| u.count_ones()).sum::<u32>() as u64 + self.far.len() as u64
+    }
+}
+
+/// Moves the head one step, and the knots following it.
+fn step<const COUNT: usize>(knots: &mut [Pos; COUNT], direction: Pos) {
+    knots[0] += direction;
+    for i in 1..COUNT {
+        let old_tail = knots[i];
+        knots[i] = follows(old_tail, knots[i - 1]);
+        if old_tail == knots[i] {
+            break;
+        }
+    }
+}
+
+pub fn solve<const COUNT: usize>(motions: &[Motion]) -> u64 {
     let mut knots: [Pos; COUNT] = [Pos::default(); COUNT];
-    let indexes: [usize; COUNT] = std::array::from_fn(|i| i);
-    let mut visited = [0usize; 1024 * 1024 / 64];
-    set_bit((0, 0), &mut visited);
-    for direction in directions(input) {
-        let head = &mut knots[0];
-        *head = add_vec(*head, direction);
-        for window in indexes.windows(2) {
-            let head = knots[window[0]];
-            let tail = &mut knots[window[1]];
-            let old_tail = *tail;
-            *tail = follows(*tail, head);
-            if old_tail == *tail {
-                break;
+    let mut visited = Visited::new();
+    for direction in directions(motions) {
+        step(&mut knots, direction);
+        visited.insert(knots[COUNT - 1]);
+    }
+    visited.count()
+}
+
+/// Both parts, reading the input a line at a time instead of holding it.
+/// The tail of part 1 is the knot after the head of part 2's rope.
+#[cfg(feature = "std")]
+pub fn solve_reader(reader: impl BufRead) -> Result<(u64, u64), ReadError> {
+    let mut knots = [Pos::ZERO; 10];
+    let (mut visited1, mut visited2) = (Visited::new(), Visited::new());
+    for_each_line(Day09::DAY, reader, |source, line| {
+        if !line.is_empty() {
+            let (direction, move_count) = parse_motion(source, line)?;
+            for _ in 0..move_count {
+                step(&mut knots, direction);
+                visited1.insert(knots[1]);
+                visited2.insert(knots[9]);
             }
         }
-        let tail = knots[COUNT - 1];
-        set_bit(tail, &mut visited);
-    }
-    visited.iter().map(|u| u.count_ones()).sum::<u32>() as u64
+        Ok(())
+    })?;
+    Ok((visited1.count(), visited2.count()))
 }
 
-fn directions(input: &str) -> impl Iterator<Item = Pos> + '_ {
-    input.lines().filter(|line| !line.is_empty()).flat_map(|s| {
-        let mut tokens = s.split_whitespace();
-        let direction = match tokens.next().unwrap() {
-            "L" => (-1, 0),
-            "R" => (1, 0),
-            "U" => (0, -1),
-            "D" => (0, 1),
-            _ => unreachable!(),
-        };
-        let move_count = tokens.next().unwrap().parse::<usize>().unwrap();
-        std::iter::repeat(direction).take(move_count)
-    })
+pub fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+    let source = Source::new(Day09::DAY, input);
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_motion(source, line))
+        .collect()
+}
+
+fn parse_motion(source: Source, line: &str) -> Result<Motion, ParseError> {
+    let (direction, move_count) = source.split_once(line, " ")?;
+    let direction = match direction {
+        "L" => Pos::LEFT,
+        "R" => Pos::RIGHT,
+        "U" => Pos::UP,
+        "D" => Pos::DOWN,
+        _ => return Err(source.error(direction, "expected L, R, U or D")),
+    };
+    Ok((direction, source.parse(move_count)?))
 }
 
-fn add_vec(vec1: Pos, vec2: Pos) -> Pos {
-    (vec1.0 + vec2.0, vec1.1 + vec2.1)
+fn directions(motions: &[Motion]) -> impl Iterator<Item = Pos> + '_ {
+    motions
+        .iter()
+        .flat_map(|&(direction, move_count)| core::iter::repeat_n(direction, move_count))
 }
 
 fn follows(tail: Pos, head: Pos) -> Pos {
-    let x_diff = head.0 - tail.0;
-    let y_diff = head.1 - tail.1;
-    if x_diff.abs() > 1 || y_diff.abs() > 1 {
-        add_vec(tail, (x_diff.signum(), y_diff.signum()))
+    if tail.chebyshev(head) > 1 {
+        tail + (head - tail).signum()
     } else {
         tail
     }
@@ -74,18 +172,34 @@ fn follows(tail: Pos, head: Pos) -> Pos {
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
     const TEST_INPUT2: &str = include_str!("test_input2.txt");
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 13);
-        assert_eq!(part1(INPUT), 6391);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 13);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 6391);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT2), 36);
-        assert_eq!(part2(INPUT), 2593);
+        assert_eq!(part2(&parse(TEST_INPUT2).unwrap()), 36);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 2593);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_solve_reader() {
+        for input in [TEST_INPUT, TEST_INPUT2, INPUT] {
+            let motions = parse(input).unwrap();
+            let expected = (part1(&motions), part2(&motions));
+            assert_eq!(solve_reader(input.as_bytes()).unwrap(), expected);
+        }
+        // far from the start, where visits are kept in the ordered set
+        assert_eq!(
+            solve_reader("R 2000\nL 2000\n".as_bytes()).unwrap(),
+            (2000, 1992)
+        );
     }
 }
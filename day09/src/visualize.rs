@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+use std::io;
+
+use visual::{Cell, Color, Frame, Recorder};
+
+use crate::{directions, follows, Motion, Pos};
+
+/// The most cells `visualize` shows, the view following the head when the
+/// rope goes further.
+const VIEW_SIZE: Pos = Pos::new(80, 40);
+
+/// Moves a rope of `knot_count` knots, recording a frame after each step
+/// of the head: the knots, over the start `s` and the positions the tail
+/// visited.
+pub fn visualize(
+    motions: &[Motion],
+    knot_count: usize,
+    recorder: &mut dyn Recorder,
+) -> io::Result<()> {
+    let (min, max) = directions(motions)
+        .scan(Pos::ZERO, |head, direction| {
+            *head += direction;
+            Some(*head)
+        })
+        .fold((Pos::ZERO, Pos::ZERO), |(min, max), head| {
+            (min.min(head), max.max(head))
+        });
+    let size = (max - min + Pos::new(1, 1)).min(VIEW_SIZE);
+    let mut knots = vec![Pos::ZERO; knot_count.max(1)];
+    let mut visited = HashSet::from([Pos::ZERO]);
+    for direction in directions(motions) {
+        knots[0] += direction;
+        for i in 1..knots.len() {
+            knots[i] = follows(knots[i], knots[i - 1]);
+        }
+        visited.insert(*knots.last().unwrap());
+        // the tail never leaves the box around the head's path
+        let corner = (knots[0] - Pos::new(size.x / 2, size.y / 2))
+            .max(min)
+            .min(max - size + Pos::new(1, 1));
+        let mut frame = Frame::filled(
+            size.x as usize,
+            size.y as usize,
+            Cell::new('.', Color::BLACK),
+        );
+        for &pos in &visited {
+            frame.set(pos - corner, Cell::new('#', Color::GREEN));
+        }
+        frame.set(Pos::ZERO - corner, Cell::new('s', Color::BLUE));
+        for (i, &knot) in knots.iter().enumerate().rev() {
+            let cell = match i {
+                0 => Cell::new('H', Color::RED),
+                1 if knots.len() == 2 => Cell::new('T', Color::YELLOW),
+                _ => Cell::new(char::from_digit(i as u32 % 10, 10).unwrap(), Color::YELLOW),
+            };
+            frame.set(knot - corner, cell);
+        }
+        recorder.record(&frame)?;
+    }
+    recorder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_visualize() {
+        let mut frames = vec![];
+        visualize(&parse(TEST_INPUT).unwrap(), 2, &mut frames).unwrap();
+        assert_eq!(frames.len(), 24);
+        assert_eq!(
+            frames.last().unwrap().to_ascii(),
+            "..##..\n...##.\n.TH##.\n....#.\ns###..\n"
+        );
+    }
+}
@@ -1,58 +1,153 @@
-pub const INPUT: &str = include_str!("input.txt");
+use std::io::BufRead;
+use std::iter::repeat_n;
 
-use std::{convert::identity, iter::repeat};
+use common::{for_each_line, ParseError, ReadError, Solution, SolveError, Source};
 
-fn signal_strengths(input: &str) -> impl Iterator<Item = i32> + '_ {
-    cycles(input).enumerate().map(|(i, x)| (i + 1) as i32 * x)
+/// The pixels in a row of the screen.
+pub const SCREEN_WIDTH: usize = 40;
+/// The rows of the screen.
+pub const SCREEN_HEIGHT: usize = 6;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instr {
+    Noop,
+    Addx(i32),
 }
 
-fn cycles(input: &str) -> impl Iterator<Item = i32> + '_ {
-    let mut x: i32 = 1;
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed<'a> = Vec<Instr>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(program: &Vec<Instr>, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(program))
+    }
+
+    fn part2(program: &Vec<Instr>, _: &()) -> Result<String, SolveError> {
+        Ok(part2(program))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+    let source = Source::new(Day10::DAY, input);
     input
         .lines()
         .filter(|l| !l.is_empty())
-        .flat_map(move |line| {
-            let mut tokens = line.split_whitespace();
-            let instr = tokens.next().unwrap();
-            match instr {
-                "noop" => repeat(x).take(1),
-                "addx" => {
-                    let result = repeat(x).take(2);
-                    let to_add = tokens.next().unwrap().parse::<i32>().unwrap();
+        .map(|line| parse_instr(source, line))
+        .collect()
+}
+
+fn parse_instr(source: Source, line: &str) -> Result<Instr, ParseError> {
+    match line.split_once(' ') {
+        None if line == "noop" => Ok(Instr::Noop),
+        Some(("addx", value)) => Ok(Instr::Addx(source.parse(value)?)),
+        _ => Err(source.error(line, "expected noop or addx")),
+    }
+}
+
+fn signal_strengths(program: &[Instr]) -> impl Iterator<Item = i32> + '_ {
+    cycles(program).enumerate().map(|(i, x)| (i + 1) as i32 * x)
+}
+
+fn cycles(program: &[Instr]) -> impl Iterator<Item = i32> + '_ {
+    let mut x: i32 = 1;
+    program.iter().flat_map(move |instr| match instr {
+        Instr::Noop => repeat_n(x, 1),
+        Instr::Addx(to_add) => {
+            let result = repeat_n(x, 2);
+            x += to_add;
+            result
+        }
+    })
+}
+
+pub fn part1(program: &[Instr]) -> i32 {
+    signal_strengths(program).skip(19).step_by(40).take(6).sum()
+}
+
+pub fn screen_chars(program: &[Instr]) -> impl Iterator<Item = char> + '_ {
+    cycles(program).enumerate().flat_map(|(i, x)| pixel(i, x))
+}
+
+/// The pixel drawn during the `i`th cycle, from 0, and the line break
+/// ending its row if it is the last.
+fn pixel(i: usize, x: i32) -> impl Iterator<Item = char> {
+    let cursor = (x - 1)..=(x + 1);
+    let index = (i % SCREEN_WIDTH) as i32;
+    [
+        Some(if cursor.contains(&index) { '#' } else { '.' }),
+        if index == SCREEN_WIDTH as i32 - 1 {
+            Some('\n')
+        } else {
+            None
+        },
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Like `cycles`, for a program read a line at a time: calls `on_cycle`
+/// with the value of X during each cycle. Blank lines are skipped.
+pub fn cycles_reader(reader: impl BufRead, mut on_cycle: impl FnMut(i32)) -> Result<(), ReadError> {
+    let mut x: i32 = 1;
+    for_each_line(Day10::DAY, reader, |source, line| {
+        if !line.is_empty() {
+            match parse_instr(source, line)? {
+                Instr::Noop => on_cycle(x),
+                Instr::Addx(to_add) => {
+                    on_cycle(x);
+                    on_cycle(x);
                     x += to_add;
-                    result
                 }
-                _ => unreachable!("{instr} op unknown."),
             }
-        })
-}
-
-pub fn part1(input: &str) -> i32 {
-    signal_strengths(input).skip(19).step_by(40).take(6).sum()
+        }
+        Ok(())
+    })
 }
 
-pub fn screen_chars(input: &str) -> impl Iterator<Item = char> + '_ {
-    cycles(input)
-        .map(|x| (x - 1)..=(x + 1))
-        .enumerate()
-        .flat_map(|(i, cursor)| {
-            let index = i as i32 % 40;
-            [
-                Some(if cursor.contains(&index) { '#' } else { '.' }),
-                if index == 39 { Some('\n') } else { None },
-            ]
-        })
-        .filter_map(identity)
+/// Both parts, reading the program a line at a time instead of holding it.
+/// Returns part 1, and calls `on_row` with each row of the screen of part 2
+/// as soon as it is drawn, line break included, the last one even if
+/// unfinished.
+pub fn solve_reader(reader: impl BufRead, mut on_row: impl FnMut(&str)) -> Result<i32, ReadError> {
+    let mut strengths = 0;
+    let mut row = String::with_capacity(SCREEN_WIDTH + 1);
+    let mut i = 0;
+    cycles_reader(reader, |x| {
+        let cycle = i as i32 + 1;
+        if cycle % 40 == 20 && cycle <= 220 {
+            strengths += cycle * x;
+        }
+        row.extend(pixel(i, x));
+        if row.ends_with('\n') {
+            on_row(&row);
+            row.clear();
+        }
+        i += 1;
+    })?;
+    if !row.is_empty() {
+        on_row(&row);
+    }
+    Ok(strengths)
 }
 
-pub fn part2(input: &str) {
-    screen_chars(input).for_each(|c| print!("{}", c));
+pub fn part2(program: &[Instr]) -> String {
+    screen_chars(program).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
     const SMALL_PROGRAM: &str = "noop
 addx 3
@@ -63,13 +158,16 @@ addx -5
 
     #[test]
     fn test_x_values() {
-        assert_eq!(cycles(SMALL_PROGRAM).count(), 5);
-        assert_eq!(cycles(SMALL_PROGRAM).collect::<Vec<i32>>(), [1, 1, 1, 4, 4]);
+        assert_eq!(cycles(&parse(SMALL_PROGRAM).unwrap()).count(), 5);
+        assert_eq!(
+            cycles(&parse(SMALL_PROGRAM).unwrap()).collect::<Vec<i32>>(),
+            [1, 1, 1, 4, 4]
+        );
     }
 
     #[test]
     fn test_signal_strengths() {
-        let strengths = signal_strengths(TEST_INPUT)
+        let strengths = signal_strengths(&parse(TEST_INPUT).unwrap())
             .skip(19)
             .step_by(40)
             .collect::<Vec<_>>();
@@ -78,23 +176,38 @@ addx -5
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 13140);
-        assert_eq!(part1(INPUT), 11220);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 13140);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 11220);
     }
 
     #[test]
     fn test_screen_chars() {
         let mut result = String::new();
-        screen_chars(TEST_INPUT).for_each(|c| result.push(c));
+        screen_chars(&parse(TEST_INPUT).unwrap()).for_each(|c| result.push(c));
         assert_eq!(
             result.trim().lines().collect::<Vec<_>>(),
             TEST_SCREEN.trim().lines().collect::<Vec<_>>()
         );
         let mut result = String::new();
-        screen_chars(INPUT).for_each(|c| result.push(c));
+        screen_chars(&parse(INPUT).unwrap()).for_each(|c| result.push(c));
         assert_eq!(
             result.trim().lines().collect::<Vec<_>>(),
             PART2_RESULT.trim().lines().collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn test_solve_reader() {
+        for input in [SMALL_PROGRAM, TEST_INPUT, INPUT] {
+            let program = parse(input).unwrap();
+            let mut rows = vec![];
+            let strengths = solve_reader(input.as_bytes(), |row| rows.push(row.to_string()));
+            assert_eq!(strengths.unwrap(), part1(&program));
+            assert_eq!(rows.concat(), part2(&program));
+            assert!(rows.iter().all(|row| row.len() <= SCREEN_WIDTH + 1));
+        }
+        let mut xs = vec![];
+        cycles_reader(SMALL_PROGRAM.as_bytes(), |x| xs.push(x)).unwrap();
+        assert_eq!(xs, [1, 1, 1, 4, 4]);
+    }
 }
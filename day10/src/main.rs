@@ -1,7 +0,0 @@
-use cark_aoc_helper::*;
-use day10::*;
-
-fn main() {
-    exec_and_print("Part1", || part1(INPUT));
-    exec_printing_duration("Part2", || part2(INPUT));
-}
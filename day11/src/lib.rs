@@ -1,7 +1,9 @@
 use std::cmp::Reverse;
 
-pub const INPUT: &str = include_str!("input.txt");
+use common::{groups, ParseError, Solution, SolveError, Source};
+use rayon::prelude::*;
 
+#[derive(Debug, Clone)]
 struct Monkey {
     op: Op,
     test: Test,
@@ -10,30 +12,30 @@ struct Monkey {
 }
 
 impl Monkey {
-    fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Option<Self> {
+    fn parse<'a>(
+        source: Source,
+        lines: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Self, ParseError> {
+        let line = next_line(source, lines)?;
+        let _monkey_id: usize =
+            source.parse(source.strip_prefix(line, "Monkey ")?.trim_end_matches(':'))?;
+        let items = source.strip_prefix(next_line(source, lines)?.trim(), "Starting items:")?;
+        let items = items
+            .split(',')
+            .map(|worry| source.parse(worry.trim()))
+            .collect::<Result<_, _>>()?;
+        let op = source.strip_prefix(next_line(source, lines)?.trim(), "Operation: new =")?;
+        let op = Op::parse(source, op)?;
+        let test = Test::parse(source, lines)?;
         if let Some(line) = lines.next() {
-            let _monkey_id = (line.as_bytes()[7] - b'0') as usize;
-            let items = lines
-                .next()
-                .unwrap()
-                .split(':')
-                .skip(1)
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|worry| worry.trim().parse().unwrap())
-                .collect();
-            let op = Op::parse(lines.next().unwrap().split('=').skip(1).next().unwrap());
-            let test = Test::parse(lines);
-            Some(Self {
-                op,
-                test,
-                items,
-                inspection_count: 0,
-            })
-        } else {
-            None
+            return Err(source.error(line, "expected a blank line after the monkey"));
         }
+        Ok(Self {
+            op,
+            test,
+            items,
+            inspection_count: 0,
+        })
     }
 
     fn moves(&mut self, keep_calm: bool) -> impl Iterator<Item = (u64, usize)> + '_ {
@@ -44,7 +46,7 @@ impl Monkey {
             *inspection_count += 1;
             let mut worry = op.apply(*worry);
             if keep_calm {
-                worry = worry / 3
+                worry /= 3
             }
             let next_monkey = test.test(worry);
             (worry, next_monkey)
@@ -52,6 +54,16 @@ impl Monkey {
     }
 }
 
+fn next_line<'a>(
+    source: Source,
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> Result<&'a str, ParseError> {
+    lines
+        .next()
+        .ok_or_else(|| source.eof("expected the rest of the monkey"))
+}
+
+#[derive(Debug, Clone)]
 struct Test {
     true_monkey: usize,
     false_monkey: usize,
@@ -59,40 +71,26 @@ struct Test {
 }
 
 impl Test {
-    fn parse<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Self {
-        let divisor = lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse()
-            .unwrap();
-        let true_monkey = lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse()
-            .unwrap();
-        let false_monkey = lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse()
-            .unwrap();
-        Self {
+    fn parse<'a>(
+        source: Source,
+        lines: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Self, ParseError> {
+        let mut last_number = |prefix| {
+            let line = next_line(source, lines)?.trim();
+            source.parse(source.strip_prefix(line, prefix)?)
+        };
+        let divisor = last_number("Test: divisible by ")?;
+        let true_monkey = last_number("If true: throw to monkey ")? as usize;
+        let false_monkey = last_number("If false: throw to monkey ")? as usize;
+        Ok(Self {
             divisor,
             false_monkey,
             true_monkey,
-        }
+        })
     }
 
     fn test(&self, worry: u64) -> usize {
-        if worry % self.divisor == 0 {
+        if worry.is_multiple_of(self.divisor) {
             self.true_monkey
         } else {
             self.false_monkey
@@ -100,21 +98,24 @@ impl Test {
     }
 }
 
+#[derive(Debug, Clone)]
 enum Op {
     Mul(Value, Value),
     Add(Value, Value),
 }
 
 impl Op {
-    fn parse(str: &str) -> Self {
-        let mut tokens = str.split_whitespace();
-        let left = Value::parse(tokens.next().unwrap());
-        let op_str = tokens.next().unwrap();
-        let right = Value::parse(tokens.next().unwrap());
+    fn parse(source: Source, str: &str) -> Result<Self, ParseError> {
+        let tokens = str.split_whitespace().collect::<Vec<_>>();
+        let &[left, op_str, right] = tokens.as_slice() else {
+            return Err(source.error(str, "expected an operation like old * 19"));
+        };
+        let left = Value::parse(source, left)?;
+        let right = Value::parse(source, right)?;
         match op_str {
-            "+" => Self::Add(left, right),
-            "*" => Self::Mul(left, right),
-            _ => unreachable!("Unknown op: {op_str}."),
+            "+" => Ok(Self::Add(left, right)),
+            "*" => Ok(Self::Mul(left, right)),
+            _ => Err(source.error(op_str, "expected + or *")),
         }
     }
 
@@ -126,17 +127,18 @@ impl Op {
     }
 }
 
+#[derive(Debug, Clone)]
 enum Value {
     Old,
     Literal(u64),
 }
 
 impl Value {
-    fn parse(str: &str) -> Self {
+    fn parse(source: Source, str: &str) -> Result<Self, ParseError> {
         if str == "old" {
-            Self::Old
+            Ok(Self::Old)
         } else {
-            Self::Literal(str.parse().unwrap())
+            Ok(Self::Literal(source.parse(str)?))
         }
     }
 
@@ -148,28 +150,29 @@ impl Value {
     }
 }
 
-struct KeepAwayGame {
+#[derive(Debug, Clone)]
+pub struct KeepAwayGame {
     monkeys: Vec<Monkey>,
     max_worry: u64,
     moves: Vec<(u64, usize)>,
 }
 
 impl KeepAwayGame {
-    fn parse(input: &str) -> Self {
-        let mut monkeys = Vec::with_capacity(16);
-        let mut lines = input.lines().filter(|l| !l.is_empty());
-        while let Some(monkey) = Monkey::parse(&mut lines) {
-            monkeys.push(monkey);
-        }
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(Day11::DAY, input);
+        let monkeys = groups(source, |lines| {
+            Monkey::parse(source, &mut lines.iter().copied())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
         let max_worry = monkeys
             .iter()
             .map(|monkey| monkey.test.divisor)
             .product::<u64>();
-        KeepAwayGame {
+        Ok(KeepAwayGame {
             monkeys,
             max_worry,
             moves: Vec::with_capacity(64),
-        }
+        })
     }
 
     fn round(&mut self, keep_calm: bool) {
@@ -195,45 +198,170 @@ impl KeepAwayGame {
             self.round(keep_calm)
         }
     }
+
+    /// How many times each monkey inspects a single item, starting with
+    /// `monkey` at `worry`.
+    fn inspections(
+        &self,
+        mut monkey: usize,
+        mut worry: u64,
+        round_count: usize,
+        keep_calm: bool,
+    ) -> Vec<u64> {
+        let mut counts = vec![0; self.monkeys.len()];
+        let mut round = 0;
+        while round < round_count {
+            let current = &self.monkeys[monkey];
+            counts[monkey] += 1;
+            worry = current.op.apply(worry);
+            if keep_calm {
+                worry /= 3
+            }
+            let next_monkey = current.test.test(worry);
+            worry %= self.max_worry;
+            // a monkey whose turn is over this round gets to it next round
+            if next_monkey <= monkey {
+                round += 1;
+            }
+            monkey = next_monkey;
+        }
+        counts
+    }
 }
 
-fn solve(input: &str, rounds: usize, keep_calm: bool) -> u64 {
-    let mut game = KeepAwayGame::parse(input);
+fn solve(game: &KeepAwayGame, rounds: usize, keep_calm: bool) -> u64 {
+    let mut game = game.clone();
     game.run_rounds(rounds, keep_calm);
     game.monkeys
         .sort_by_key(|monkey| Reverse(monkey.inspection_count));
     game.monkeys[0].inspection_count * game.monkeys[1].inspection_count
 }
 
-pub fn part1(input: &str) -> u64 {
-    solve(input, 20, true)
+/// Same as `solve`, following each item on its own: where an item goes
+/// never depends on the other items.
+fn solve_parallel(game: &KeepAwayGame, rounds: usize, keep_calm: bool) -> u64 {
+    let items = game
+        .monkeys
+        .iter()
+        .enumerate()
+        .flat_map(|(i, monkey)| monkey.items.iter().map(move |&worry| (i, worry)))
+        .collect::<Vec<_>>();
+    let mut counts = items
+        .par_iter()
+        .map(|&(monkey, worry)| game.inspections(monkey, worry, rounds, keep_calm))
+        .reduce(
+            || vec![0; game.monkeys.len()],
+            |mut total, counts| {
+                total.iter_mut().zip(counts).for_each(|(t, c)| *t += c);
+                total
+            },
+        );
+    counts.sort_unstable_by_key(|&count| Reverse(count));
+    counts[0] * counts[1]
 }
 
-pub fn part2(input: &str) -> u64 {
-    solve(input, 10000, false)
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed<'a> = KeepAwayGame;
+    type Params = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<KeepAwayGame, ParseError> {
+        parse(input)
+    }
+
+    fn part1(game: &KeepAwayGame, _: &()) -> Result<u64, SolveError> {
+        Ok(part1(game))
+    }
+
+    fn part2(game: &KeepAwayGame, _: &()) -> Result<u64, SolveError> {
+        Ok(part2(game))
+    }
+
+    fn part2_parallel(game: &KeepAwayGame, _: &()) -> Result<u64, SolveError> {
+        Ok(part2_parallel(game))
+    }
+}
+
+pub fn parse(input: &str) -> Result<KeepAwayGame, ParseError> {
+    KeepAwayGame::parse(input)
+}
+
+pub fn part1(game: &KeepAwayGame) -> u64 {
+    solve(game, 20, true)
+}
+
+pub fn part2(game: &KeepAwayGame) -> u64 {
+    solve(game, 10000, false)
+}
+
+pub fn part2_parallel(game: &KeepAwayGame) -> u64 {
+    solve_parallel(game, 10000, false)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
     fn test_parsing() {
-        let kag = KeepAwayGame::parse(TEST_INPUT);
+        let kag = KeepAwayGame::parse(TEST_INPUT).unwrap();
         assert_eq!(kag.monkeys.len(), 4);
     }
 
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_INPUT.replace("new = old * 19", "new = old ^ 19");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (11, 3, 24));
+        assert_eq!(error.text, "^");
+        let input = TEST_INPUT.replace("If false: throw to monkey 3", "If false: monkey 3");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 5));
+        let error = parse("Monkey 0:\n  Starting items: 79, 98\n").unwrap_err();
+        assert_eq!(error.line, 3);
+        let input = TEST_INPUT
+            .replace("\r\n", "\n")
+            .replace("\n\nMonkey 1", "\nMonkey 1");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (7, "Monkey 1:"));
+    }
+
+    #[test]
+    fn test_parse_spacing() {
+        let input = TEST_INPUT
+            .replace("\r\n", "\n")
+            .replace('\n', " \r\n")
+            .replace(" \r\n \r\n", "\r\n\r\n\r\n");
+        let game = parse(&input).unwrap();
+        assert_eq!(game.monkeys.len(), 4);
+        assert_eq!(part1(&game), 10605);
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 10605);
-        assert_eq!(part1(INPUT), 182293);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 10605);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 182293);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 2713310158);
-        assert_eq!(part2(INPUT), 54832778815);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 2713310158);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 54832778815);
+    }
+
+    #[test]
+    fn test_solve_parallel() {
+        for input in [TEST_INPUT, INPUT] {
+            let game = parse(input).unwrap();
+            assert_eq!(solve_parallel(&game, 20, true), part1(&game));
+            assert_eq!(part2_parallel(&game), part2(&game));
+        }
     }
 }
@@ -1,136 +1,215 @@
 // turns out that bfs is faster than a-star even for part1
 
-pub const INPUT: &str = include_str!("input.txt");
-
 use std::collections::VecDeque;
+use std::io;
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
-struct Pos {
-    x: i32,
-    y: i32,
-}
-
-impl Pos {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
+use common::{ParseError, Solution, SolveError, Source};
+use grid::{Grid, Pos};
+use visual::{Cell, Color, Frame, Recorder};
 
-struct Board {
-    width: usize,
-    height: usize,
-    elevation: Vec<u8>,
+pub struct Board {
+    elevation: Grid<u8>,
     start: Pos,
     dest: Pos,
 }
 
 impl Board {
-    fn parse(input: &str) -> Self {
-        let mut result = Board {
-            width: 0,
-            height: 0,
-            elevation: vec![],
-            start: Pos::new(0, 0),
-            dest: Pos::new(0, 0),
-        };
-        input
-            .lines()
-            .filter(|line| !line.is_empty())
-            .enumerate()
-            .for_each(|(y, line)| {
-                result.height = y + 1;
-                line.as_bytes().iter().enumerate().for_each(|(x, &byte)| {
-                    if result.width <= x {
-                        result.width = x + 1;
-                    }
-                    result.elevation.push(match byte {
-                        b'S' => {
-                            result.start = Pos::new(x as i32, y as i32);
-                            0
-                        }
-                        b'E' => {
-                            result.dest = Pos::new(x as i32, y as i32);
-                            b'z' - b'a'
-                        }
-                        letter => letter - b'a',
-                    });
-                })
-            });
-        result
-    }
-
-    fn pos_index(&self, pos: Pos) -> usize {
-        pos.x as usize + pos.y as usize * self.width
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(Day12::DAY, input);
+        let (mut start, mut dest) = (None, None);
+        let elevation = Grid::parse(source, "expected an elevation letter", |pos, c| match c {
+            'S' => {
+                start = Some(pos);
+                Some(0)
+            }
+            'E' => {
+                dest = Some(pos);
+                Some(b'z' - b'a')
+            }
+            'a'..='z' => Some(c as u8 - b'a'),
+            _ => None,
+        })?;
+        Ok(Board {
+            elevation,
+            start: start.ok_or_else(|| source.eof("expected a start square S"))?,
+            dest: dest.ok_or_else(|| source.eof("expected a best signal square E"))?,
+        })
     }
 
-    fn bfs(&self, is_dest: impl Fn(Pos) -> bool) -> i32 {
-        let mut tentative_dist = Vec::with_capacity(self.elevation.len());
-        tentative_dist.resize(self.elevation.len(), i32::MAX);
-        tentative_dist[self.pos_index(self.dest)] = 0;
-        let mut open_list = VecDeque::with_capacity(self.elevation.len());
+    /// Searches back from the destination for the closest square where
+    /// `is_dest`, calling `on_layer` with the distances found so far each
+    /// time the search goes one step further. Returns that square, and the
+    /// distances.
+    fn bfs(
+        &self,
+        is_dest: impl Fn(Pos) -> bool,
+        mut on_layer: impl FnMut(&Grid<i32>),
+    ) -> (Pos, Grid<i32>) {
+        let mut tentative_dist =
+            Grid::new(self.elevation.width(), self.elevation.height(), i32::MAX);
+        tentative_dist[self.dest] = 0;
+        let mut open_list =
+            VecDeque::with_capacity(self.elevation.width() * self.elevation.height());
         open_list.push_back(self.dest);
+        let mut layer = 0;
         while let Some(current) = open_list.pop_front() {
-            let current_index = self.pos_index(current);
-            let current_elevation = self.elevation[current_index];
-            let curr_dist = tentative_dist[current_index];
+            let current_elevation = self.elevation[current];
+            let curr_dist = tentative_dist[current];
+            if curr_dist > layer {
+                on_layer(&tentative_dist);
+                layer = curr_dist;
+            }
             if is_dest(current) {
-                return curr_dist;
+                return (current, tentative_dist);
             }
-            for neighbour in
-                [(0, -1), (-1, 0), (1, 0), (0, 1)]
-                    .into_iter()
-                    .filter_map(|(dx, dy)| {
-                        let x = current.x + dx;
-                        let y = current.y + dy;
-                        if (0..self.width as i32).contains(&x)
-                            && (0..self.height as i32).contains(&y)
-                        {
-                            Some(Pos::new(x, y))
-                        } else {
-                            None
-                        }
-                    })
-            {
-                let neighbour_index = self.pos_index(neighbour);
-                let neighbour_dist = &mut tentative_dist[neighbour_index];
-                let neighbour_elevation = self.elevation[neighbour_index];
-                if (current_elevation as i32 - 1..).contains(&(neighbour_elevation as i32)) {
-                    if curr_dist + 1 < *neighbour_dist {
-                        *neighbour_dist = curr_dist + 1;
-                        open_list.push_back(neighbour);
-                    }
+            for neighbour in self.elevation.neighbors4(current) {
+                let neighbour_dist = &mut tentative_dist[neighbour];
+                let neighbour_elevation = self.elevation[neighbour];
+                if (current_elevation as i32 - 1..).contains(&(neighbour_elevation as i32))
+                    && curr_dist + 1 < *neighbour_dist
+                {
+                    *neighbour_dist = curr_dist + 1;
+                    open_list.push_back(neighbour);
                 }
             }
         }
         unreachable!()
     }
+
+    /// The way from `start` to the destination, following the distances
+    /// found by `bfs`.
+    fn path(&self, dist: &Grid<i32>, start: Pos) -> Vec<Pos> {
+        let mut result = vec![start];
+        let mut current = start;
+        while current != self.dest {
+            current = self
+                .elevation
+                .neighbors4(current)
+                .find(|&next| {
+                    dist[next] == dist[current] - 1
+                        && self.elevation[next] <= self.elevation[current] + 1
+                })
+                .unwrap();
+            result.push(current);
+        }
+        result
+    }
+
+    fn frame(&self, dist: &Grid<i32>) -> Frame {
+        Frame::from_grid(&self.elevation, |pos, &elevation| {
+            let ch = if pos == self.start {
+                'S'
+            } else if pos == self.dest {
+                'E'
+            } else {
+                (b'a' + elevation) as char
+            };
+            let height = elevation as f32 / 25.0;
+            let color = if dist[pos] == i32::MAX {
+                Color::BLACK.mix(Color::GREY, height)
+            } else {
+                Color::BLUE.mix(Color::WHITE, height)
+            };
+            Cell::new(ch, color)
+        })
+    }
 }
 
-pub fn part1(input: &str) -> i32 {
-    let board = Board::parse(input);
-    board.bfs(|pos| pos == board.start)
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed<'a> = Board;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Board, ParseError> {
+        parse(input)
+    }
+
+    fn part1(board: &Board, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(board))
+    }
+
+    fn part2(board: &Board, _: &()) -> Result<i32, SolveError> {
+        Ok(part2(board))
+    }
 }
 
-pub fn part2(input: &str) -> i32 {
-    let board = Board::parse(input);
-    board.bfs(|pos| board.elevation[board.pos_index(pos)] == 0)
+pub fn parse(input: &str) -> Result<Board, ParseError> {
+    Board::parse(input)
+}
+
+pub fn part1(board: &Board) -> i32 {
+    let (start, dist) = board.bfs(|pos| pos == board.start, |_| {});
+    dist[start]
+}
+
+pub fn part2(board: &Board) -> i32 {
+    let (start, dist) = board.bfs(|pos| board.elevation[pos] == 0, |_| {});
+    dist[start]
+}
+
+/// Records the search spreading from the destination, a frame per step,
+/// then the path found. Part 2 searches for any lowest square instead of
+/// the start.
+pub fn visualize(board: &Board, any_lowest: bool, recorder: &mut dyn Recorder) -> io::Result<()> {
+    let mut result = Ok(());
+    let is_dest = |pos| {
+        if any_lowest {
+            board.elevation[pos] == 0
+        } else {
+            pos == board.start
+        }
+    };
+    let (start, dist) = board.bfs(is_dest, |dist| {
+        if result.is_ok() {
+            result = recorder.record(&board.frame(dist));
+        }
+    });
+    result?;
+    let mut frame = board.frame(&dist);
+    for pos in board.path(&dist, start) {
+        let cell = frame.get(pos).unwrap();
+        frame.set(pos, Cell::new(cell.ch, Color::RED));
+    }
+    recorder.record(&frame)?;
+    recorder.finish()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 31);
-        assert_eq!(part1(INPUT), 383);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 31);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 383);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let board = parse(TEST_INPUT).unwrap();
+        let mut frames = vec![];
+        visualize(&board, false, &mut frames).unwrap();
+        // a frame per step of the shortest path, and the path
+        assert_eq!(frames.len(), 31 + 1);
+        let last = frames.last().unwrap();
+        let path = board
+            .elevation
+            .positions()
+            .filter(|&pos| last.get(pos).unwrap().color == Color::RED)
+            .count();
+        assert_eq!(path, 31 + 1);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 29);
-        assert_eq!(part2(INPUT), 377);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 29);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 377);
     }
 }
@@ -1,9 +1,11 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use day13::*;
 
+const INPUT: &str = include_str!("../src/input.txt");
+
 pub fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("part 1", |b| b.iter(|| part1(INPUT)));
-    c.bench_function("part 2", |b| b.iter(|| part2(INPUT)));
+    c.bench_function("part 1", |b| b.iter(|| part1(&parse(INPUT).unwrap())));
+    c.bench_function("part 2", |b| b.iter(|| part2(&parse(INPUT).unwrap())));
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,24 +1,103 @@
-pub const INPUT: &str = include_str!("input.txt");
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn part1(input: &str) -> usize {
-    let mut lines = input.lines().filter(|line| !line.is_empty());
-    std::iter::from_fn(move || lines.next().map(|line1| (line1, lines.next().unwrap())))
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use common::{groups, ParseError, Solution, SolveError, Source};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Parsed<'a> = Vec<&'a str>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(packets: &Vec<&str>, _: &()) -> Result<usize, SolveError> {
+        Ok(part1(packets))
+    }
+
+    fn part2(packets: &Vec<&str>, _: &()) -> Result<usize, SolveError> {
+        Ok(part2(packets))
+    }
+}
+
+/// The packets, pairs being separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(Day13::DAY, input);
+    let mut packets = Vec::new();
+    for pair in groups(source, move |lines| parse_pair(source, lines)) {
+        packets.extend(pair?);
+    }
+    Ok(packets)
+}
+
+fn parse_pair<'a>(source: Source, lines: &[&'a str]) -> Result<[&'a str; 2], ParseError> {
+    for packet in lines {
+        check_packet(source, packet)?;
+    }
+    match *lines {
+        [left, right] => Ok([left, right]),
+        [_, _, extra, ..] => Err(source.error(extra, "expected a blank line after the pair")),
+        _ => {
+            let last = lines[lines.len() - 1];
+            Err(source.error(&last[last.len()..], "expected a second packet for the pair"))
+        }
+    }
+}
+
+fn check_packet(source: Source, packet: &str) -> Result<(), ParseError> {
+    source.check_chars(
+        packet,
+        |c| c.is_ascii_digit() || "[],".contains(c),
+        "unexpected character in packet",
+    )?;
+    if !packet.starts_with('[') {
+        return Err(source.error(packet, "expected a list"));
+    }
+    let mut depth = 0;
+    for (i, c) in packet.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 && i + 1 < packet.len() {
+            return Err(source.error(&packet[i + 1..], "unexpected text after the packet"));
+        }
+    }
+    if depth != 0 {
+        return Err(source.error(packet, "unclosed list"));
+    }
+    Ok(())
+}
+
+pub fn part1(packets: &[&str]) -> usize {
+    packets
+        .chunks(2)
         .enumerate()
-        .filter_map(|(i, (l, r))| is_ordered(&mut tokenize(l), &mut tokenize(r)).then_some(i + 1))
+        .filter_map(|(i, pair)| {
+            is_ordered(&mut tokenize(pair[0]), &mut tokenize(pair[1])).then_some(i + 1)
+        })
         .sum()
 }
 
-pub fn part2(input: &str) -> usize {
-    fn index_of_virtual_packet(input: &str, virtual_packet: &str) -> usize {
-        1 + input
-            .lines()
-            .filter(|line| !line.is_empty())
+pub fn part2(packets: &[&str]) -> usize {
+    fn index_of_virtual_packet(packets: &[&str], virtual_packet: &str) -> usize {
+        1 + packets
+            .iter()
             .filter(|line| is_ordered(&mut tokenize(line), &mut tokenize(virtual_packet)))
             .count()
     }
 
-    let index2 = index_of_virtual_packet(input, "[[2]]");
-    let index6 = index_of_virtual_packet(input, "[[6]]") + 1;
+    let index2 = index_of_virtual_packet(packets, "[[2]]");
+    let index6 = index_of_virtual_packet(packets, "[[6]]") + 1;
     index2 * index6
 }
 
@@ -32,7 +111,7 @@ enum Token {
 fn tokenize(input: &str) -> impl Iterator<Item = Token> + '_ {
     use Token::*;
     let mut bytes = input.as_bytes().iter().peekable();
-    std::iter::from_fn(move || loop {
+    core::iter::from_fn(move || loop {
         if let Some(&byte) = bytes.next() {
             match byte {
                 b'[' => return Some(StartList),
@@ -41,7 +120,7 @@ fn tokenize(input: &str) -> impl Iterator<Item = Token> + '_ {
                 digit => {
                     let mut value = digit - b'0';
                     while let Some(&byte) = bytes.peek() {
-                        if (b'0'..=b'9').contains(byte) {
+                        if byte.is_ascii_digit() {
                             bytes.next();
                             value = value * 10 + byte - b'0';
                         } else {
@@ -103,6 +182,7 @@ fn is_ordered<'a>(
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
@@ -153,15 +233,28 @@ mod tests {
             &mut tokenize("[1,[2,[3,[4,[5,6,0]]]],8,9]")
         ));
     }
+
+    #[test]
+    fn test_parse() {
+        let packets = parse("[1]\r\n[2] \r\n\r\n\r\n[[3]]\r\n[]\r\n").unwrap();
+        assert_eq!(packets, ["[1]", "[2]", "[[3]]", "[]"]);
+        let error = parse("[1]\n[2]\n\n[3]\n\n[4]\n[5]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 4));
+        let error = parse("[1]\n[2]\n[3]\n").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (3, "[3]"));
+        let error = parse("[1]\n[2}\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 13);
-        assert_eq!(part1(INPUT), 5588);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 13);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 5588);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 140);
-        assert_eq!(part2(INPUT), 23958);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 140);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 23958);
     }
 }
@@ -1,69 +1,41 @@
 // 150 320
-pub const INPUT: &str = include_str!("input.txt");
 
-use std::ops::{Add, Sub};
+use std::io;
+
+use common::{ParseError, Solution, SolveError, Source};
+pub use grid::Pos;
+use grid::{Grid, DOWN};
+use visual::{Cell, Color, Frame, Recorder};
 
 const DROP_POINT: Pos = Pos::new(500, 0);
-const DOWN: Pos = Pos::new(0, 1);
 const DOWN_LEFT: Pos = Pos::new(-1, 1);
 const DOWN_RIGHT: Pos = Pos::new(1, 1);
 const DIRECTIONS: [Pos; 3] = [DOWN, DOWN_LEFT, DOWN_RIGHT];
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos {
-    x: i32,
-    y: i32,
-}
-
-impl Pos {
-    const fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Add for Pos {
-    type Output = Pos;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Pos::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-
-impl Sub for Pos {
-    type Output = Pos;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Pos::new(self.x - rhs.x, self.y - rhs.y)
-    }
-}
+const AIR: Cell = Cell::new('.', Color::BLACK);
+const ROCK: Cell = Cell::new('#', Color::GREY);
+const SAND: Cell = Cell::new('o', Color::YELLOW);
+const SOURCE: Cell = Cell::new('+', Color::WHITE);
 
 struct Reservoir {
-    occupancy: Vec<bool>,
+    occupancy: Grid<bool>,
     top_left: Pos,
-    bottom_right: Pos,
-    size: Pos,
 }
 
 impl Reservoir {
-    fn from_input(input: &str, with_bottom: bool) -> Self {
-        let (mut top_left, mut bottom_right) = find_bounds(input);
+    fn from_paths(paths: &[Vec<Pos>], with_bottom: bool) -> Self {
+        let (mut top_left, mut bottom_right) = find_bounds(paths);
         bottom_right.y += 2;
         top_left.x = 500 - bottom_right.y - 1;
         bottom_right.x = 500 + bottom_right.y + 1;
         let size = (bottom_right - top_left) + Pos::new(1, 1);
-        let occupancy_size = (size.x * size.y) as usize;
-        let mut occupancy = Vec::with_capacity(occupancy_size);
-        occupancy.resize(occupancy_size, false);
         let mut result = Self {
-            occupancy,
+            occupancy: Grid::new(size.x as usize, size.y as usize, false),
             top_left,
-            bottom_right,
-            size,
         };
-        input
-            .lines()
-            .filter(|line| !line.is_empty())
-            .flat_map(parse_path)
+        paths
+            .iter()
+            .flat_map(|path| trace_path(path))
             .for_each(|pos| {
                 result.set_occupied(pos);
             });
@@ -75,27 +47,19 @@ impl Reservoir {
         result
     }
 
-    fn pos_index(&self, pos: Pos) -> usize {
-        let with_offset = pos - self.top_left;
-        (with_offset.x + with_offset.y * self.size.x) as usize
-    }
-
     fn set_occupied(&mut self, pos: Pos) {
-        let index = self.pos_index(pos);
-        self.occupancy[index] = true;
+        self.occupancy[pos - self.top_left] = true;
     }
 
     fn is_occupied(&self, pos: Pos) -> bool {
-        if self.in_bounds(pos) {
-            self.occupancy[self.pos_index(pos)]
-        } else {
-            false
-        }
+        self.occupancy
+            .get(pos - self.top_left)
+            .copied()
+            .unwrap_or(false)
     }
 
     fn in_bounds(&self, pos: Pos) -> bool {
-        (self.top_left.x..=self.bottom_right.x).contains(&pos.x)
-            && (self.top_left.y..=self.bottom_right.y).contains(&pos.y)
+        self.occupancy.in_bounds(pos - self.top_left)
     }
 
     fn depth_first(&mut self, pos: Pos) -> (bool, u64) {
@@ -117,30 +81,68 @@ impl Reservoir {
             (false, children_count + 1)
         }
     }
+
+    /// Where a grain of sand dropped at `pos` comes to rest, if it doesn't
+    /// fall out.
+    fn drop_grain(&self, mut pos: Pos) -> Option<Pos> {
+        loop {
+            if !self.in_bounds(pos) {
+                return None;
+            }
+            match DIRECTIONS
+                .into_iter()
+                .map(|dir| pos + dir)
+                .find(|&new_pos| !self.is_occupied(new_pos))
+            {
+                Some(new_pos) => pos = new_pos,
+                None => return Some(pos),
+            }
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::from_grid(
+            &self.occupancy,
+            |_, &occupied| {
+                if occupied {
+                    ROCK
+                } else {
+                    AIR
+                }
+            },
+        );
+        frame.set(DROP_POINT - self.top_left, SOURCE);
+        frame
+    }
 }
 
-fn parse_vertices(line: &str) -> impl Iterator<Item = Pos> + '_ {
-    line.split(" -> ").map(|vertex| {
-        let mut coords = vertex.split(',').map(|v| v.parse().unwrap());
-        Pos::new(coords.next().unwrap(), coords.next().unwrap())
-    })
+fn parse_vertices(source: Source, line: &str) -> Result<Vec<Pos>, ParseError> {
+    let mut vertices: Vec<Pos> = Vec::new();
+    for vertex in line.split(" -> ") {
+        let (x, y) = source.split_once(vertex, ",")?;
+        let pos = Pos::new(source.parse(x)?, source.parse(y)?);
+        if let Some(last) = vertices.last() {
+            if last.x != pos.x && last.y != pos.y {
+                return Err(source.error(vertex, "expected a horizontal or vertical line"));
+            }
+        }
+        vertices.push(pos);
+    }
+    Ok(vertices)
 }
 
-fn parse_path(line: &str) -> impl Iterator<Item = Pos> + '_ {
-    let mut vertices = parse_vertices(line);
+fn trace_path(vertices: &[Pos]) -> impl Iterator<Item = Pos> + '_ {
+    let mut vertices = vertices.iter().copied();
     let mut current = vertices.next().unwrap();
     std::iter::once(current).chain(
         std::iter::from_fn(move || {
             if let Some(next_vertex) = vertices.next() {
-                let direction = Pos::new(
-                    (next_vertex.x - current.x).signum(),
-                    (next_vertex.y - current.y).signum(),
-                );
+                let direction = (next_vertex - current).signum();
                 let mut current_edge = current;
                 current = next_vertex;
                 let mut done = false;
                 Some(std::iter::from_fn(move || {
-                    current_edge = current_edge + direction;
+                    current_edge += direction;
                     if done {
                         None
                     } else {
@@ -158,53 +160,88 @@ fn parse_path(line: &str) -> impl Iterator<Item = Pos> + '_ {
     )
 }
 
-fn find_bounds(input: &str) -> (Pos, Pos) {
+fn find_bounds(paths: &[Vec<Pos>]) -> (Pos, Pos) {
+    paths.iter().flatten().fold(
+        (DROP_POINT, DROP_POINT),
+        |(top_left, bottom_right), &item| (top_left.min(item), bottom_right.max(item)),
+    )
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Parsed<'a> = Vec<Vec<Pos>>;
+    type Params = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Pos>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(paths: &Vec<Vec<Pos>>, _: &()) -> Result<u64, SolveError> {
+        Ok(part1(paths))
+    }
+
+    fn part2(paths: &Vec<Vec<Pos>>, _: &()) -> Result<u64, SolveError> {
+        Ok(part2(paths))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<Pos>>, ParseError> {
+    let source = Source::new(Day14::DAY, input);
     input
         .lines()
         .filter(|line| !line.is_empty())
-        .flat_map(|line| parse_vertices(line))
-        .fold(
-            (Pos::new(500, 0), Pos::new(500, 0)),
-            |(top_left, bottom_right), item| {
-                (
-                    Pos::new(top_left.x.min(item.x), top_left.y.min(item.y)),
-                    Pos::new(bottom_right.x.max(item.x), bottom_right.y.max(item.y)),
-                )
-            },
-        )
+        .map(|line| parse_vertices(source, line))
+        .collect()
 }
 
-pub fn part1(input: &str) -> u64 {
-    Reservoir::from_input(input, false)
+pub fn part1(paths: &[Vec<Pos>]) -> u64 {
+    Reservoir::from_paths(paths, false)
         .depth_first(DROP_POINT)
         .1
 }
 
-pub fn part2(input: &str) -> u64 {
-    Reservoir::from_input(input, true).depth_first(DROP_POINT).1
+pub fn part2(paths: &[Vec<Pos>]) -> u64 {
+    Reservoir::from_paths(paths, true).depth_first(DROP_POINT).1
 }
 
-#[allow(dead_code)]
-fn draw(reservoir: &Reservoir) {
-    for (i, val) in reservoir.occupancy.iter().enumerate() {
-        if i % reservoir.size.x as usize == 0 {
-            println!();
+/// Drops the sand grain by grain, recording a frame each time one comes to
+/// rest, with the floor of part 2 if `with_bottom`.
+pub fn visualize(
+    paths: &[Vec<Pos>],
+    with_bottom: bool,
+    recorder: &mut dyn Recorder,
+) -> io::Result<()> {
+    let mut reservoir = Reservoir::from_paths(paths, with_bottom);
+    let mut frame = reservoir.frame();
+    recorder.record(&frame)?;
+    while let Some(pos) = reservoir.drop_grain(DROP_POINT) {
+        reservoir.set_occupied(pos);
+        frame.set(pos - reservoir.top_left, SAND);
+        recorder.record(&frame)?;
+        if pos == DROP_POINT {
+            break;
         }
-        print!("{}", if *val { '#' } else { '.' });
     }
-    println!();
+    recorder.finish()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
-    fn test_parse_path() {
+    fn test_trace_path() {
+        let line = "498,4 -> 498,6 -> 496,6";
+        let vertices = parse_vertices(Source::new(14, line), line).unwrap();
         assert_eq!(
-            parse_path("498,4 -> 498,6 -> 496,6").collect::<Vec<_>>(),
+            trace_path(&vertices).collect::<Vec<_>>(),
             [
                 Pos::new(498, 4),
                 Pos::new(498, 5),
@@ -218,27 +255,43 @@ mod tests {
     #[test]
     fn test_find_bounds() {
         assert_eq!(
-            find_bounds(TEST_INPUT),
+            find_bounds(&parse(TEST_INPUT).unwrap()),
             (Pos::new(494, 0), Pos::new(503, 9))
         )
     }
 
     #[test]
     fn test_from_input() {
-        // let reservoir = Reservoir::from_input(TEST_INPUT, false);
-        // draw(&reservoir)
-        // assert!(false);
+        let reservoir = Reservoir::from_paths(&parse(TEST_INPUT).unwrap(), false);
+        let text = reservoir.frame().to_ascii();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[0], "............+............");
+        assert_eq!(lines[4], "..........#...##.........");
+        assert_eq!(lines[9], "......#########..........");
+    }
+
+    #[test]
+    fn test_visualize() {
+        let paths = parse(TEST_INPUT).unwrap();
+        for (with_bottom, grains) in [(false, 24), (true, 93)] {
+            let mut frames = vec![];
+            visualize(&paths, with_bottom, &mut frames).unwrap();
+            assert_eq!(frames.len(), grains + 1);
+            let sand = frames.last().unwrap().to_ascii().matches('o').count();
+            assert_eq!(sand, grains);
+        }
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 24);
-        assert_eq!(part1(INPUT), 779);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 24);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 779);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 93);
-        assert_eq!(part2(INPUT), 27426);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 93);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 27426);
     }
 }
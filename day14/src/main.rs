@@ -1,7 +0,0 @@
-use cark_aoc_helper::*;
-use day14::*;
-
-fn main() {
-    exec_and_print("Part1", || part1(INPUT));
-    exec_and_print("Part2", || part2(INPUT));
-}
@@ -1,8 +1,7 @@
-use std::convert::identity;
+use common::{ParseError, Solution, SolveError, Source, Vec2};
+use rayon::prelude::*;
 
-pub const INPUT: &str = include_str!("input.txt");
-
-type Pos = (i64, i64);
+pub type Pos = Vec2<i64>;
 type Edge = (Pos, Pos);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -20,21 +19,65 @@ impl Range {
     }
 }
 
-fn manhattan(p1: Pos, p2: Pos) -> i64 {
-    (p1.0 - p2.0).abs() + (p1.1 - p2.1).abs()
-}
-
 #[derive(Debug)]
-struct Sensor {
+pub struct Sensor {
     pos: Pos,
     beacon: Pos,
     dist: i64,
 }
 
-pub fn part1(input: &str, y: i64) -> u64 {
-    let sensors = parse(input);
+pub struct Day15;
+
+/// The row scanned by part 1, and the bounds of the search area of part 2.
+pub struct Params {
+    pub row: i64,
+    pub max_pos: Pos,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            row: 2000000,
+            max_pos: Pos::new(4000000, 4000000),
+        }
+    }
+}
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Parsed<'a> = Vec<Sensor>;
+    type Params = Params;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Sensor>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(sensors: &Vec<Sensor>, params: &Params) -> Result<u64, SolveError> {
+        Ok(part1(sensors, params.row))
+    }
+
+    fn part2(sensors: &Vec<Sensor>, params: &Params) -> Result<u64, SolveError> {
+        better_part2(sensors, params.max_pos).ok_or_else(no_hole)
+    }
+
+    fn part2_parallel(sensors: &Vec<Sensor>, params: &Params) -> Result<u64, SolveError> {
+        better_part2_parallel(sensors, params.max_pos).ok_or_else(no_hole)
+    }
+}
+
+fn no_hole() -> SolveError {
+    SolveError::new(
+        Day15::DAY,
+        2,
+        "the sensors reach every position of the search area",
+    )
+}
+
+pub fn part1(sensors: &[Sensor], y: i64) -> u64 {
     // get ranges covered by each sensor on this line
-    let mut ranges = sensor_ranges_at_line(&sensors, y);
+    let mut ranges = sensor_ranges_at_line(sensors, y);
 
     // merge the ranges
     ranges.sort_unstable_by_key(|r| r.start);
@@ -47,16 +90,33 @@ pub fn part1(input: &str, y: i64) -> u64 {
         .iter()
         .map(|range| {
             // there can be multiple beacons on a line
-            beacons_in_range(&sensors, range, y, &mut included_beacons);
+            beacons_in_range(sensors, range, y, &mut included_beacons);
             (range.end - range.start + 1) as usize - included_beacons.len()
         })
         .sum::<usize>() as u64
 }
 
-pub fn better_part2(input: &str, max_pos: Pos) -> u64 {
-    let sensors = parse(input);
+/// The tuning frequency of the position no sensor reaches, or `None` if
+/// they reach the whole search area.
+pub fn better_part2(sensors: &[Sensor], max_pos: Pos) -> Option<u64> {
+    // now it's like brute force part2, on our interesting lines only
+    interesting_ys(sensors, max_pos)
+        .into_iter()
+        .find_map(|y| hole_at_line(sensors, y))
+}
+
+/// Same as `better_part2`, scanning the interesting lines on several
+/// threads.
+pub fn better_part2_parallel(sensors: &[Sensor], max_pos: Pos) -> Option<u64> {
+    interesting_ys(sensors, max_pos)
+        .into_par_iter()
+        .find_map_first(|y| hole_at_line(sensors, y))
+}
+
+/// The lines where edges of the scanning areas meet, in the search area.
+fn interesting_ys(sensors: &[Sensor], max_pos: Pos) -> Vec<i64> {
     // find edges of all diamond shaped scanning areas
-    let edges = edges(&sensors);
+    let edges = edges(sensors);
     let mut interesting_ys = Vec::with_capacity(edges.len() * edges.len() * 4);
     for i in 0..edges.len() {
         for j in 0..edges.len() {
@@ -64,83 +124,70 @@ pub fn better_part2(input: &str, max_pos: Pos) -> u64 {
             // keeping only the y values
             points_of_interest(&edges, i, j)
                 .into_iter()
-                .filter_map(identity)
+                .flatten()
                 .for_each(|y| {
-                    if y >= 0 && y <= max_pos.1 {
+                    if y >= 0 && y <= max_pos.y {
                         interesting_ys.push(y);
                     }
                 });
         }
     }
-    // iterate over our interesting lines, skipping duplicates
-    let mut last_y = -1;
-    for y in interesting_ys.into_iter() {
-        if y == last_y {
-            continue;
-        }
-        last_y = y;
-        // now it's like brute force part2
-        let mut ranges = sensor_ranges_at_line(&sensors, y);
-        ranges.sort_unstable_by_key(|r| r.start);
-        let merged = merge_ranges(&ranges);
-        if merged.len() > 1 {
-            let x = merged[0].end as u64 + 1;
-            let y = y as u64;
-            return 4000000 * x + y;
-        }
-    }
-    unreachable!()
+    // skipping duplicates
+    interesting_ys.dedup();
+    interesting_ys
 }
 
-pub fn part2(input: &str, max_pos: Pos) -> u64 {
+pub fn part2(sensors: &[Sensor], max_pos: Pos) -> Option<u64> {
     // Naive version, same as part1 for each line,
     // only we're now looking for a line with 2 merged ranges.
     // the hole is between these 2 ranges
-    let sensors = parse(input);
-    for y in 0..=max_pos.1 {
-        let mut ranges = sensor_ranges_at_line(&sensors, y);
-        ranges.sort_unstable_by_key(|r| r.start);
-        let merged = merge_ranges(&ranges);
-        if merged.len() > 1 {
-            let x = merged[0].end as u64 + 1;
-            let y = y as u64;
-            //println!("({x}, {y})"); // 2639657
-            return 4000000 * x + y;
-        }
+    (0..=max_pos.y).find_map(|y| hole_at_line(sensors, y))
+}
+
+/// Same as `part2`, scanning the lines on several threads.
+pub fn part2_parallel(sensors: &[Sensor], max_pos: Pos) -> Option<u64> {
+    (0..=max_pos.y)
+        .into_par_iter()
+        .find_map_first(|y| hole_at_line(sensors, y))
+}
+
+/// The tuning frequency of the position no sensor reaches on line `y`, if
+/// there is one.
+fn hole_at_line(sensors: &[Sensor], y: i64) -> Option<u64> {
+    let mut ranges = sensor_ranges_at_line(sensors, y);
+    ranges.sort_unstable_by_key(|r| r.start);
+    let merged = merge_ranges(&ranges);
+    if merged.len() > 1 {
+        let x = merged[0].end as u64 + 1;
+        let y = y as u64;
+        Some(4000000 * x + y)
+    } else {
+        None
     }
-    unreachable!();
 }
 
-fn parse_pos(s: &str) -> Pos {
-    let numbers = s.split("x=").skip(1).next().unwrap();
-    let mut numbers = numbers.split(',');
-    let x = numbers.next().unwrap().parse().unwrap();
-    let y = numbers
-        .next()
-        .unwrap()
-        .split("y=")
-        .skip(1)
-        .next()
-        .unwrap()
-        .parse()
-        .unwrap();
-    (x, y)
+fn parse_pos(source: Source, s: &str) -> Result<Pos, ParseError> {
+    let (x, y) = source.split_once(s, ", ")?;
+    let x = source.parse(source.strip_prefix(x, "x=")?)?;
+    let y = source.parse(source.strip_prefix(y, "y=")?)?;
+    Ok(Pos::new(x, y))
 }
 
-fn parse(input: &str) -> Vec<Sensor> {
+pub fn parse(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    let source = Source::new(Day15::DAY, input);
     let mut result = Vec::with_capacity(128);
     for line in input.lines().filter(|line| !line.is_empty()) {
-        let mut split = line.split(':');
-        let (sensor_str, beacon_str) = (split.next().unwrap(), split.next().unwrap());
-        let sensor_pos = parse_pos(sensor_str);
-        let beacon_pos = parse_pos(beacon_str);
+        let rest = source.strip_prefix(line, "Sensor at ")?;
+        let (sensor_str, beacon_str) = source.split_once(rest, ": closest beacon is at ")?;
+        let sensor_pos = parse_pos(source, sensor_str)?;
+        let beacon_pos = parse_pos(source, beacon_str)?;
         result.push(Sensor {
             beacon: beacon_pos,
             pos: sensor_pos,
-            dist: manhattan(beacon_pos, sensor_pos),
+            dist: beacon_pos.manhattan(sensor_pos),
         })
     }
-    result
+    Ok(result)
 }
 
 fn sensor_ranges_at_line(sensors: &[Sensor], y: i64) -> Vec<Range> {
@@ -151,12 +198,12 @@ fn sensor_ranges_at_line(sensors: &[Sensor], y: i64) -> Vec<Range> {
 }
 
 fn sensor_range_at_line(sensor: &Sensor, y: i64) -> Option<Range> {
-    let half_size = sensor.dist - (y - sensor.pos.1).abs();
+    let half_size = sensor.dist - (y - sensor.pos.y).abs();
     let range_size = half_size * 2 + 1;
     if range_size > 0 {
         Some(Range::new(
-            sensor.pos.0 - half_size,
-            sensor.pos.0 + half_size,
+            sensor.pos.x - half_size,
+            sensor.pos.x + half_size,
         ))
     } else {
         None
@@ -191,10 +238,10 @@ fn merge_ranges(sorted_ranges: &[Range]) -> Vec<Range> {
     }
 }
 
-fn beacons_in_range(sensors: &[Sensor], range: &Range, y: i64, into_vec: &mut Vec<(i64, i64)>) {
+fn beacons_in_range(sensors: &[Sensor], range: &Range, y: i64, into_vec: &mut Vec<Pos>) {
     into_vec.clear();
     for s in sensors {
-        if s.beacon.1 == y && range.contains(s.beacon.0) && !into_vec.contains(&s.beacon) {
+        if s.beacon.y == y && range.contains(s.beacon.x) && !into_vec.contains(&s.beacon) {
             into_vec.push(s.beacon);
         }
     }
@@ -207,10 +254,10 @@ fn edges(sensors: &[Sensor]) -> Vec<Edge> {
     let mut edges = Vec::with_capacity(sensors.len() * 4);
     for sensor in sensors {
         let p = sensor.pos;
-        let left = (p.0 - sensor.dist, p.1);
-        let right = (p.0 + sensor.dist, p.1);
-        let top = (p.0, p.1 - sensor.dist);
-        let bottom = (p.0, p.1 + sensor.dist);
+        let left = p + Pos::LEFT * sensor.dist;
+        let right = p + Pos::RIGHT * sensor.dist;
+        let top = p + Pos::UP * sensor.dist;
+        let bottom = p + Pos::DOWN * sensor.dist;
         edges.push((left, top));
         edges.push((bottom, right));
         edges.push((top, right));
@@ -228,30 +275,35 @@ fn points_of_interest(edges: &[Edge], index1: usize, index2: usize) -> [Option<i
     // we've been adding edges two by two having the same direction
     let edge1 = edges[index1];
     let edge2 = edges[index2];
-    let params1 = line_params(edge1.0, edge1.1);
-    let params2 = line_params(edge2.0, edge2.1);
+    let (Some(params1), Some(params2)) =
+        (line_params(edge1.0, edge1.1), line_params(edge2.0, edge2.1))
+    else {
+        // the edges of a sensor on its beacon are a single position
+        return [None; 2];
+    };
     if params1.0 == params2.0 {
         // parallels
         return [None; 2];
     }
-    let [y1, y2] = y_intersection(line_params(edge1.0, edge1.1), line_params(edge2.0, edge2.1));
+    let [y1, y2] = y_intersection(params1, params2);
     [
         y1.and_then(|y| (edge_contains_y(edge1, y) && edge_contains_y(edge2, y)).then_some(y)),
         y2.and_then(|y| (edge_contains_y(edge1, y) && edge_contains_y(edge2, y)).then_some(y)),
     ]
 }
 
-fn edge_contains_y(((_, y1), (_, y2)): Edge, y: i64) -> bool {
-    y >= y1.min(y2) && y <= y1.max(y2)
+fn edge_contains_y((p1, p2): Edge, y: i64) -> bool {
+    y >= p1.y.min(p2.y) && y <= p1.y.max(p2.y)
 }
 
-fn line_params(p1: Pos, p2: Pos) -> (i64, i64) {
+/// `None` if the points are the same, giving no line.
+fn line_params(p1: Pos, p2: Pos) -> Option<(i64, i64)> {
     //A line has this formula y = ax + b
     //in our case the slope is always 1 or -1 so ints are ok
-    let a = (p2.1 - p1.1) / (p2.0 - p1.0);
+    let a = (p2.y - p1.y).checked_div(p2.x - p1.x)?;
     debug_assert!(a == 1 || a == -1);
-    let b = p1.1 - a * p1.0;
-    (a, b)
+    let b = p1.y - a * p1.x;
+    Some((a, b))
 }
 
 fn y_intersection((a1, b1): (i64, i64), (a2, b2): (i64, i64)) -> [Option<i64>; 2] {
@@ -271,38 +323,56 @@ fn y_intersection((a1, b1): (i64, i64), (a2, b2): (i64, i64)) -> [Option<i64>; 2
 mod tests {
     use std::collections::BTreeSet;
 
+    use visual::{Cell, Color, Frame};
+
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
     fn test_parsing() {
-        parse(TEST_INPUT);
+        parse(TEST_INPUT).unwrap();
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+                     Sensor at x=9, y=1b: closest beacon is at x=10, y=16\n";
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (15, 2, 18));
+        assert_eq!(error.text, "1b");
+        let error = parse("Sensor at x=9, y=16: beacon at x=10, y=16").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
     }
 
     #[test]
     fn test_line_params() {
-        let a = line_params((2, 0), (4, 2));
-        assert_eq!(a, (1, -2));
+        let a = line_params(Pos::new(2, 0), Pos::new(4, 2));
+        assert_eq!(a, Some((1, -2)));
+        assert_eq!(line_params(Pos::new(2, 0), Pos::new(2, 0)), None);
     }
 
     #[test]
     fn test_y_intersection() {
         let a = y_intersection((-1, 2), (1, -2));
         assert_eq!(a, [Some(0), None]);
-        let a = y_intersection(line_params((1, 1), (4, 4)), line_params((1, 3), (3, 1)));
+        let a = y_intersection(
+            line_params(Pos::new(1, 1), Pos::new(4, 4)).unwrap(),
+            line_params(Pos::new(1, 3), Pos::new(3, 1)).unwrap(),
+        );
         assert_eq!(a, [Some(2), None]);
-        let a = y_intersection(line_params((0, 0), (3, 3)), line_params((0, 3), (3, 0)));
+        let a = y_intersection(
+            line_params(Pos::new(0, 0), Pos::new(3, 3)).unwrap(),
+            line_params(Pos::new(0, 3), Pos::new(3, 0)).unwrap(),
+        );
         assert_eq!(a, [Some(1), Some(2)]);
     }
 
     #[test]
     fn test_points_of_interest() {
-        let mut edges = vec![];
-        edges.push(((0, 2), (2, 0)));
-        edges.push(((2, 4), (4, 2)));
-        edges.push(((0, 2), (2, 4)));
-        edges.push(((2, 0), (4, 2)));
+        let edges = vec![
+            (Pos::new(0, 2), Pos::new(2, 0)),
+            (Pos::new(2, 4), Pos::new(4, 2)),
+            (Pos::new(0, 2), Pos::new(2, 4)),
+            (Pos::new(2, 0), Pos::new(4, 2)),
+        ];
         assert_eq!(points_of_interest(&edges, 0, 0), [None, None]);
         assert_eq!(points_of_interest(&edges, 0, 1), [None, None]);
         assert_eq!(points_of_interest(&edges, 0, 2), [Some(2), None]);
@@ -312,82 +382,180 @@ mod tests {
             for j in 0..edges.len() {
                 points_of_interest(&edges, i, j)
                     .into_iter()
-                    .filter_map(identity)
+                    .flatten()
                     .for_each(|y| {
                         all_points.insert(y);
                     });
             }
         }
-        println!("{:?}", all_points);
         assert_eq!(all_points.len(), 3);
     }
 
     #[test]
     fn test_sensor_range_at_line() {
-        let sensors = parse(TEST_INPUT);
+        let sensors = parse(TEST_INPUT).unwrap();
         let sensor = &sensors[6];
         let results = [
             1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 17, 15, 13, 11, 9, 7, 5, 3, 1,
         ];
         for (y, result) in (-2..16).zip(results) {
-            let r = sensor_range_at_line(&sensor, y).unwrap();
+            let r = sensor_range_at_line(sensor, y).unwrap();
             assert_eq!(r.end - r.start + 1, result);
         }
-        println!("{:?}", sensors[6]);
     }
 
-    #[test]
-    fn draw_covered() {
-        let sensors = parse(TEST_INPUT);
-        println!("using sensor_ranges_at_line");
-        for y in 0..20 {
-            let mut chars = [b' '; 21];
-            sensor_ranges_at_line(&sensors, y).iter().for_each(|r| {
-                (r.start..=r.end).for_each(|x| {
-                    if x >= 0 && x <= 20 {
-                        chars[x as usize] = b'#';
-                    }
-                });
-            });
-            println!("{}", std::str::from_utf8(&chars).unwrap());
+    /// The search area of the sample, `#` where one of `ranges` on the
+    /// line reaches.
+    fn covered_frame(ranges_at_line: impl Fn(i64) -> Vec<Range>) -> Frame {
+        let mut frame = Frame::filled(21, 21, Cell::new('.', Color::BLACK));
+        for y in 0..=20 {
+            for r in ranges_at_line(y) {
+                for x in r.start.max(0)..=r.end.min(20) {
+                    let pos = grid::Pos::new(x as i32, y as i32);
+                    frame.set(pos, Cell::new('#', Color::WHITE));
+                }
+            }
         }
-        println!();
-        println!("using merged ranges");
-        for y in 0..20 {
+        frame
+    }
+
+    #[test]
+    fn test_covered_frame() {
+        let sensors = parse(TEST_INPUT).unwrap();
+        let frame = covered_frame(|y| sensor_ranges_at_line(&sensors, y));
+        let merged = covered_frame(|y| {
             let mut ranges = sensor_ranges_at_line(&sensors, y);
             ranges.sort_unstable_by_key(|r| r.start);
-            let mut chars = [b' '; 21];
-            let merged = merge_ranges(&ranges);
-            print!("{}", merged.len());
-            if merged.len() > 1 {
-                print!("{:?}", &merged);
-            }
-            merged.iter().for_each(|r| {
-                (r.start..=r.end).for_each(|x| {
-                    if x >= 0 && x <= 20 {
-                        chars[x as usize] = b'#';
-                    }
-                });
-            });
-            println!("{}", std::str::from_utf8(&chars).unwrap());
-        }
-        //assert!(false)
+            merge_ranges(&ranges)
+        });
+        assert_eq!(frame, merged);
+        // only the distress beacon is out of reach
+        let text = frame.to_ascii();
+        assert_eq!(text.matches('.').count(), 1);
+        assert_eq!(text.lines().nth(11).unwrap().find('.'), Some(14));
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT, 10), 26);
-        assert_eq!(part1(INPUT, 2000000), 4748135);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap(), 10), 26);
+        assert_eq!(part1(&parse(INPUT).unwrap(), 2000000), 4748135);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT, (20, 20)), 56000011);
-        //assert_eq!(part2(INPUT, (4000000, 4000000)), 13743542639657);
+        assert_eq!(
+            part2(&parse(TEST_INPUT).unwrap(), Pos::new(20, 20)),
+            Some(56000011)
+        );
+        //assert_eq!(part2(&parse(INPUT).unwrap(), Pos::new(4000000, 4000000)), 13743542639657);
+        assert_eq!(
+            part2_parallel(&parse(TEST_INPUT).unwrap(), Pos::new(20, 20)),
+            Some(56000011)
+        );
     }
     #[test]
     fn test_better_part2() {
-        assert_eq!(better_part2(TEST_INPUT, (20, 20)), 56000011);
-        assert_eq!(better_part2(INPUT, (4000000, 4000000)), 13743542639657);
+        assert_eq!(
+            better_part2(&parse(TEST_INPUT).unwrap(), Pos::new(20, 20)),
+            Some(56000011)
+        );
+        assert_eq!(
+            better_part2(&parse(INPUT).unwrap(), Pos::new(4000000, 4000000)),
+            Some(13743542639657)
+        );
+        assert_eq!(
+            better_part2_parallel(&parse(INPUT).unwrap(), Pos::new(4000000, 4000000)),
+            Some(13743542639657)
+        );
+    }
+
+    #[test]
+    fn test_no_hole() {
+        // a sensor on its beacon reaches only its own position
+        let input = format!("{TEST_INPUT}Sensor at x=3, y=3: closest beacon is at x=3, y=3\n");
+        let sensors = parse(&input).unwrap();
+        assert_eq!(better_part2(&sensors, Pos::new(20, 20)), Some(56000011));
+
+        let sensors = parse("Sensor at x=1, y=1: closest beacon is at x=1, y=4\n").unwrap();
+        let max_pos = Pos::new(2, 2);
+        assert_eq!(part2(&sensors, max_pos), None);
+        assert_eq!(better_part2(&sensors, max_pos), None);
+        let params = Params { row: 0, max_pos };
+        let error = Day15::part2(&sensors, &params).unwrap_err();
+        assert_eq!((error.day, error.part), (15, 2));
+    }
+
+    /// The sensors at the given positions, none of them detecting a beacon
+    /// at `hidden`: each one's closest beacon is nearer, the number next to
+    /// the sensor picking which of the positions at that distance it is.
+    /// Sensors too close to `hidden`, or at the same position as an earlier
+    /// one, are left out.
+    ///
+    /// With sensors in the corners of a square around `hidden`, no other
+    /// position of the square can hold a beacon: every position lies
+    /// between `hidden` and a corner, so is nearer to that corner's sensor
+    /// than its beacon is.
+    fn render_sensors(hidden: (i64, i64), sensors: &[((i64, i64), u64)]) -> String {
+        let mut seen = vec![];
+        let mut result = String::new();
+        for &((x, y), choice) in sensors {
+            let dist = (x - hidden.0).abs() + (y - hidden.1).abs() - 1;
+            if dist < 1 || seen.contains(&(x, y)) {
+                continue;
+            }
+            seen.push((x, y));
+            let t = (choice % dist as u64) as i64;
+            let (dx, dy) = match choice / dist as u64 % 4 {
+                0 => (dist - t, t),
+                1 => (-t, dist - t),
+                2 => (t - dist, -t),
+                _ => (t, t - dist),
+            };
+            result += &format!(
+                "Sensor at x={x}, y={y}: closest beacon is at x={}, y={}\n",
+                x + dx,
+                y + dy
+            );
+        }
+        result
+    }
+
+    #[test]
+    fn test_better_part2_agrees() {
+        use difftest::proptest::collection::vec;
+        use difftest::proptest::prelude::*;
+
+        // A search area with a single position out of every sensor's reach,
+        // four sensors in its corners making sure of it.
+        let area = (2i64..=40).prop_flat_map(|max| {
+            let pos = move || (0..=max, 0..=max);
+            (
+                Just(max),
+                (1..max, 1..max),
+                [any::<u64>(), any::<u64>(), any::<u64>(), any::<u64>()],
+                vec((pos(), any::<u64>()), 0..10),
+            )
+        });
+        difftest::check(
+            Day15::DAY,
+            "better_part2",
+            300,
+            area,
+            |&(max, hidden, choices, ref others)| {
+                let corners = [(0, 0), (max, 0), (0, max), (max, max)];
+                let mut sensors = corners.into_iter().zip(choices).collect::<Vec<_>>();
+                sensors.extend(others);
+                render_sensors(hidden, &sensors)
+            },
+            |input| {
+                let sensors = parse(input).map_err(|e| e.to_string())?;
+                let max = sensors.iter().map(|s| s.pos.x).max().unwrap();
+                let max_pos = Pos::new(max, max);
+                let answer = part2(&sensors, max_pos);
+                difftest::same(answer, better_part2(&sensors, max_pos))?;
+                difftest::same(answer, part2_parallel(&sensors, max_pos))?;
+                difftest::same(answer, better_part2_parallel(&sensors, max_pos))
+            },
+        );
     }
 }
@@ -1,10 +1,10 @@
 use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, VecDeque},
+    collections::{HashMap, VecDeque},
     iter::once,
 };
 
-pub const INPUT: &str = include_str!("input.txt");
+use common::{ParseError, Solution, SolveError, Source};
+use rayon::prelude::*;
 
 type ValveId = usize;
 
@@ -27,44 +27,49 @@ struct DistantEdge {
 }
 
 impl Cave {
-    pub fn parse(input: &str) -> Self {
-        let mut start_valve_id = 0;
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let source = Source::new(Day16::DAY, input);
+        let mut start_valve_id = None;
         let mut valves = Vec::with_capacity(200);
         let mut index_edges = Vec::with_capacity(200);
         let mut name_indexes = HashMap::new();
-        input
-            .lines()
-            .filter(|line| !line.is_empty())
-            .for_each(|line| {
-                let mut parts = line.split(';');
-                let mut tokens = parts.next().unwrap().split_whitespace();
-                let name = tokens.nth(1).unwrap();
-                let rate = tokens
-                    .nth(2)
-                    .and_then(|s| s.split('=').nth(1))
-                    .map(|s| s.parse::<i32>().unwrap())
-                    .unwrap();
-                let edges = parts
-                    .next()
-                    .and_then(|s| {
-                        s.strip_prefix(" tunnels lead to valves ")
-                            .or(s.strip_prefix(" tunnel leads to valve "))
-                    })
-                    .map(|s| s.split(", ").collect::<Vec<_>>())
-                    .unwrap();
-                index_edges.push(edges);
-                name_indexes.insert(name, valves.len());
-                if name == "AA" {
-                    start_valve_id = valves.len()
-                }
-                valves.push(Valve {
-                    edges: vec![],
-                    rate,
-                })
-            });
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let rest = source.strip_prefix(line, "Valve ")?;
+            let (name, rest) = source.split_once(rest, " has flow rate=")?;
+            let (rate, edges) = source.split_once(rest, "; ")?;
+            let rate = source.parse::<i32>(rate)?;
+            let edges = edges
+                .strip_prefix("tunnels lead to valves ")
+                .or(edges.strip_prefix("tunnel leads to valve "))
+                .ok_or_else(|| source.error(edges, "expected the tunnels to other valves"))?
+                .split(", ")
+                .collect::<Vec<_>>();
+            // valves are visited through a bit set
+            if valves.len() == usize::BITS as usize {
+                return Err(source.error(line, "too many valves"));
+            }
+            index_edges.push(edges);
+            name_indexes.insert(name, valves.len());
+            if name == "AA" {
+                start_valve_id = Some(valves.len())
+            }
+            valves.push(Valve {
+                edges: vec![],
+                rate,
+            })
+        }
+        let start_valve_id = start_valve_id.ok_or_else(|| source.eof("expected a valve AA"))?;
         // edge fixup
         for (i, edges) in index_edges.into_iter().enumerate() {
-            valves[i].edges = edges.iter().map(|&name| name_indexes[name]).collect();
+            valves[i].edges = edges
+                .iter()
+                .map(|&name| {
+                    name_indexes
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| source.error(name, "unknown valve"))
+                })
+                .collect::<Result<_, _>>()?;
         }
 
         // we're only interested in "good" valves
@@ -95,10 +100,10 @@ impl Cave {
             })
             .collect::<Vec<_>>();
 
-        Self {
+        Ok(Self {
             start_valve_id: good_start,
             valves: good_valves,
-        }
+        })
     }
 }
 
@@ -127,178 +132,254 @@ fn find_distant_edges(valves: &[Valve<ValveId>], start_id: ValveId) -> Valve<Dis
     }
 }
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Parsed<'a> = Cave;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Cave, ParseError> {
+        Cave::parse(input)
+    }
+
+    fn part1(cave: &Cave, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(cave))
+    }
+
+    fn part2(cave: &Cave, _: &()) -> Result<i32, SolveError> {
+        Ok(part2(cave))
+    }
+
+    fn part2_parallel(cave: &Cave, _: &()) -> Result<i32, SolveError> {
+        Ok(part2_parallel(cave))
+    }
+}
+
 pub fn part1(cave: &Cave) -> i32 {
     let acceptable = (2u32.pow(cave.valves.len() as u32) - 1) & !1;
-    let mut tentative_pressures = vec![0; cave.valves.len()];
-    best_pressure(
-        &cave.valves,
-        cave.start_valve_id,
-        acceptable,
-        30,
-        &mut tentative_pressures,
-    )
+    best_pressures(&cave.valves, cave.start_valve_id, acceptable, 30)
+        .into_iter()
+        .max()
+        .unwrap()
 }
 
 pub fn part2(cave: &Cave) -> i32 {
-    let combination_count = 2u32.pow(cave.valves.len() as u32);
-    let combination_mask = combination_count as u32 - 1;
-    let mut tentative_pressures = vec![0; cave.valves.len()];
-    let mut cache = HashMap::new();
-    (0..combination_count)
-        .map(|good_valves_mask| {
-            // all combination of bits for good valves... and their converse for the elephant
-            [
-                good_valves_mask & !1,
-                (!good_valves_mask) & combination_mask & !1,
-            ]
-            .into_iter()
-            // get best pressure for this combination, both for elephant and me
-            .map(|acceptable| {
-                if let Some(&result) = cache.get(&(cave.start_valve_id, acceptable)) {
-                    result
-                } else {
-                    let result = best_pressure(
-                        &cave.valves,
-                        cave.start_valve_id,
-                        acceptable,
-                        26,
-                        &mut tentative_pressures,
-                    );
-                    cache.insert((cave.start_valve_id, acceptable), result);
-                    result
-                }
-            })
-            // sum those best pressures
-            .sum()
-        })
+    let pressures = subset_pressures(cave, false);
+    let combination_mask = (1 << cave.valves.len()) - 1;
+    (0..=combination_mask)
+        .map(|good_valves_mask| split_pressure(&pressures, combination_mask, good_valves_mask))
         .max()
         .unwrap()
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Agent {
-    id: ValveId,
-    acceptable: u32,
-    ppm: i32,
-    p: i32,
-    time_left: i32,
+/// Same as `part2`, going through the combinations on several threads.
+pub fn part2_parallel(cave: &Cave) -> i32 {
+    let pressures = subset_pressures(cave, true);
+    let combination_mask = (1 << cave.valves.len()) - 1;
+    (0..=combination_mask)
+        .into_par_iter()
+        .map(|good_valves_mask| split_pressure(&pressures, combination_mask, good_valves_mask))
+        .max()
+        .unwrap()
 }
 
-fn best_pressure(
-    valves: &[Valve<DistantEdge>],
-    start: ValveId,
-    acceptable: u32,
-    time: i32,
-    tentative_pressures: &mut [i32],
-) -> i32 {
-    tentative_pressures.iter_mut().for_each(|p| *p = 0);
-    let mut queue = BinaryHeap::new();
-    queue.push(Agent {
-        id: start,
-        ppm: 0,
-        p: 0,
-        time_left: time,
-        acceptable,
-    });
-    while let Some(agent) = queue.pop() {
-        let valve = &valves[agent.id];
-        valve.edges.iter().for_each(|de| {
-            let mask = 1 << de.valve_id;
-            let new_time_left = agent.time_left - de.dist - 1;
-            if agent.acceptable & mask != 0 && new_time_left >= 0 {
-                let new_valve = &valves[de.valve_id];
-                let eventual_p =
-                    agent.p + agent.ppm * agent.time_left + new_valve.rate * new_time_left;
-                if eventual_p > tentative_pressures[de.valve_id] {
-                    tentative_pressures[de.valve_id] = eventual_p;
-                    queue.push(Agent {
-                        ppm: agent.ppm + new_valve.rate,
-                        p: agent.p + agent.ppm * (de.dist + 1),
-                        time_left: new_time_left,
-                        acceptable: agent.acceptable & !mask,
-                        id: de.valve_id,
-                    })
-                }
+/// The best pressure released in 26 minutes opening any subset of the
+/// valves of each combination, by combination. Each pass over the table
+/// goes on several threads when `parallel`.
+fn subset_pressures(cave: &Cave, parallel: bool) -> Vec<i32> {
+    let combination_mask = (1 << cave.valves.len()) - 1;
+    let mut pressures =
+        best_pressures(&cave.valves, cave.start_valve_id, combination_mask & !1, 26);
+    for valve_id in 0..cave.valves.len() {
+        // each chunk holds the combinations without the valve, then the
+        // same ones with it
+        let half = 1 << valve_id;
+        let spread = |chunk: &mut [i32]| {
+            let (without, with) = chunk.split_at_mut(half);
+            for (with, without) in with.iter_mut().zip(without) {
+                *with = (*with).max(*without);
             }
-        });
-    }
-    tentative_pressures.iter().copied().max().unwrap()
-}
-
-impl Ord for Agent {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let result = self.time_left.cmp(&other.time_left);
-        if let Ordering::Equal = result {
-            self.ppm.cmp(&other.ppm)
+        };
+        if parallel {
+            pressures.par_chunks_mut(half * 2).for_each(spread);
         } else {
-            result
+            pressures.chunks_mut(half * 2).for_each(spread);
         }
     }
+    pressures
 }
 
-impl PartialOrd for Agent {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+/// The pressure released by opening the valves of `good_valves_mask`, the
+/// elephant opening the others.
+fn split_pressure(pressures: &[i32], combination_mask: u32, good_valves_mask: u32) -> i32 {
+    // all combination of bits for good valves... and their converse for the elephant
+    pressures[(good_valves_mask & !1) as usize]
+        + pressures[((!good_valves_mask) & combination_mask & !1) as usize]
 }
 
-impl PartialEq for Agent {
-    fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
-            && self.ppm == other.ppm
-            && self.p == other.p
-            && self.time_left == other.time_left
+/// The best pressure released in `time` minutes opening exactly the valves
+/// of each combination of `acceptable` ones, by combination; 0 for those
+/// out of reach.
+fn best_pressures(
+    valves: &[Valve<DistantEdge>],
+    start: ValveId,
+    acceptable: u32,
+    time: i32,
+) -> Vec<i32> {
+    let mut pressures = vec![0; 1 << valves.len()];
+    let mut stack = vec![(start, 0u32, 0, time)];
+    while let Some((valve_id, opened, p, time_left)) = stack.pop() {
+        pressures[opened as usize] = pressures[opened as usize].max(p);
+        for de in valves[valve_id].edges.iter() {
+            let mask = 1 << de.valve_id;
+            let new_time_left = time_left - de.dist - 1;
+            if acceptable & !opened & mask != 0 && new_time_left > 0 {
+                stack.push((
+                    de.valve_id,
+                    opened | mask,
+                    p + valves[de.valve_id].rate * new_time_left,
+                    new_time_left,
+                ));
+            }
+        }
     }
-}
-
-impl Eq for Agent {
-    fn assert_receiver_is_total_eq(&self) {}
+    pressures
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     #[test]
-    fn test_silly() {
-        let mut queue = BinaryHeap::new();
-        queue.push(Agent {
-            id: 0,
-            ppm: 0,
-            p: 0,
-            time_left: 2,
-            acceptable: 0,
-        });
-        queue.push(Agent {
-            id: 0,
-            ppm: 2,
-            p: 0,
-            time_left: 3,
-            acceptable: 0,
-        });
-        queue.push(Agent {
-            id: 0,
-            ppm: 1,
-            p: 0,
-            time_left: 3,
-            acceptable: 0,
-        });
-        let Some(a) = queue.pop() else { panic!() };
-        assert_eq!(a.time_left, 3);
-        assert_eq!(a.ppm, 2);
+    fn test_part2() {
+        assert_eq!(part2(&Cave::parse(TEST_INPUT).unwrap()), 1707);
+        assert_eq!(part2(&Cave::parse(INPUT).unwrap()), 2824);
+        assert_eq!(part2_parallel(&Cave::parse(INPUT).unwrap()), 2824);
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(part2(&Cave::parse(TEST_INPUT)), 1707);
-        assert_eq!(part2(&Cave::parse(INPUT)), 2824);
+    fn test_part1() {
+        assert_eq!(part1(&Cave::parse(TEST_INPUT).unwrap()), 1651);
+        assert_eq!(part1(&Cave::parse(INPUT).unwrap()), 2181);
     }
 
     #[test]
-    fn test_part1() {
-        assert_eq!(part1(&Cave::parse(TEST_INPUT)), 1651);
-        assert_eq!(part1(&Cave::parse(INPUT)), 2181);
+    fn test_better_order() {
+        // Found by comparing with an exhaustive search: reaching a valve
+        // with less pressure than before can still lead to the best order.
+        let cave = Cave::parse(include_str!("better_order_input.txt")).unwrap();
+        assert_eq!(part1(&cave), 1047);
+        assert_eq!(part2(&cave), 969);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_INPUT.replace("rate=13;", "rate=13,");
+        let error = Cave::parse(&input).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (16, 2, 24));
+        let input = TEST_INPUT.replace("valves CC, AA", "valves CC, ZZ");
+        let error = Cave::parse(&input).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 55, "ZZ")
+        );
+    }
+
+    /// A valve per rate, named `AA`, `AB`..., with tunnels both ways
+    /// between the valves of each pair. Tunnels from a valve to itself, and
+    /// repeated ones, are left out.
+    fn render_cave(rates: &[u32], tunnels: &[(usize, usize)]) -> String {
+        let name = |i: usize| format!("A{}", char::from(b'A' + i as u8));
+        let mut others = vec![vec![]; rates.len()];
+        for &(a, b) in tunnels {
+            if a != b && !others[a].contains(&b) {
+                others[a].push(b);
+                others[b].push(a);
+            }
+        }
+        let mut result = String::new();
+        for (i, rate) in rates.iter().enumerate() {
+            let others = others[i].iter().map(|&j| name(j)).collect::<Vec<_>>();
+            let tunnels = match others.as_slice() {
+                [other] => format!("tunnel leads to valve {other}"),
+                _ => format!("tunnels lead to valves {}", others.join(", ")),
+            };
+            result += &format!("Valve {} has flow rate={rate}; {tunnels}\n", name(i));
+        }
+        result
+    }
+
+    /// The most pressure released opening valves of `acceptable`, trying
+    /// every order.
+    fn brute_force(valves: &[Valve<DistantEdge>], id: ValveId, acceptable: u32, time: i32) -> i32 {
+        valves[id]
+            .edges
+            .iter()
+            .filter(|de| acceptable & 1 << de.valve_id != 0 && de.dist < time)
+            .map(|de| {
+                let time_left = time - de.dist - 1;
+                valves[de.valve_id].rate * time_left
+                    + brute_force(
+                        valves,
+                        de.valve_id,
+                        acceptable & !(1 << de.valve_id),
+                        time_left,
+                    )
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_best_pressure_agrees() {
+        use difftest::proptest::collection::vec;
+        use difftest::proptest::prelude::*;
+        use difftest::proptest::sample::Index;
+
+        // A connected cave: a random tree, with a few more tunnels.
+        let cave = (2usize..=8).prop_flat_map(|n| {
+            (
+                vec(0u32..=25, n),
+                vec(any::<Index>(), n),
+                vec((any::<Index>(), any::<Index>()), 0..n),
+            )
+        });
+        difftest::check(
+            Day16::DAY,
+            "best_pressure",
+            300,
+            cave,
+            |(rates, parents, extra)| {
+                let n = rates.len();
+                let mut rates = rates.clone();
+                rates[0] = 0;
+                let tunnels = (1..n)
+                    .map(|i| (i, parents[i].index(i)))
+                    .chain(extra.iter().map(|(a, b)| (a.index(n), b.index(n))))
+                    .collect::<Vec<_>>();
+                render_cave(&rates, &tunnels)
+            },
+            |input| {
+                let cave = Cave::parse(input).map_err(|e| e.to_string())?;
+                let all = (1 << cave.valves.len()) - 1;
+                let expected1 = brute_force(&cave.valves, cave.start_valve_id, all & !1, 30);
+                difftest::same(part1(&cave), expected1)?;
+                let expected2 = (0..=all)
+                    .map(|mask: u32| {
+                        brute_force(&cave.valves, cave.start_valve_id, mask & !1, 26)
+                            + brute_force(&cave.valves, cave.start_valve_id, !mask & all & !1, 26)
+                    })
+                    .max()
+                    .unwrap();
+                difftest::same(part2(&cave), expected2)?;
+                difftest::same(part2_parallel(&cave), expected2)
+            },
+        );
     }
 }
@@ -1,14 +1,19 @@
-pub const INPUT: &str = include_str!("input.txt");
+use std::collections::HashMap;
+use std::io;
+
+use common::{ParseError, Solution, SolveError, Source};
+pub use grid::Pos;
+use grid::{Grid, LEFT, RIGHT};
+use visual::{Cell, Color, Frame, Recorder};
+
 const ROCKS: &str = include_str!("shapes.txt");
 const FIELD_WIDTH: usize = 7;
 const ACTUAL_WIDTH: usize = FIELD_WIDTH + 2;
 const ROCK_TYPE_COUNT: usize = 5;
-
-type Pos = (i32, i32);
-
-fn add_pos(pos1: Pos, pos2: Pos) -> Pos {
-    (pos1.0 + pos2.0, pos1.1 + pos2.1)
-}
+/// The rows shown by `visualize`, the falling rock at the top.
+const VIEW_HEIGHT: usize = 30;
+/// The rows of the tower part 2 compares to find a cycle.
+const SURFACE_DEPTH: usize = 32;
 
 struct Rock {
     pixels: Vec<Pos>,
@@ -26,18 +31,15 @@ impl<'a> RockInstance<'a> {
     }
 
     fn pixels(&self) -> impl Iterator<Item = Pos> + '_ {
-        self.rock.pixels.iter().map(|&p| add_pos(p, self.offset))
+        self.rock.pixels.iter().map(|&p| p + self.offset)
     }
 
     fn offset_pixels(&self, offset: Pos) -> impl Iterator<Item = Pos> + '_ {
-        self.pixels().map(move |p| {
-            let result = add_pos(p, offset);
-            //println!(" {:?}", result);
-            result
-        })
+        self.pixels().map(move |p| p + offset)
     }
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Copy)]
 struct RowStat {
     max_height: usize,
@@ -45,7 +47,7 @@ struct RowStat {
 }
 
 struct Field {
-    rows: Vec<[bool; ACTUAL_WIDTH]>,
+    rows: Grid<bool>,
     row_stats: Vec<Option<RowStat>>,
     max_height: usize,
 }
@@ -53,39 +55,37 @@ struct Field {
 impl Field {
     fn new() -> Self {
         Self {
-            rows: vec![],
+            rows: Grid::new(ACTUAL_WIDTH, 0, false),
             row_stats: vec![],
             max_height: 1,
         }
     }
 
     fn occupied(&mut self, pos: Pos) -> bool {
-        if pos.1 == 0 || pos.0 == 0 || pos.0 == ACTUAL_WIDTH as i32 - 1 {
+        if pos.y == 0 || pos.x == 0 || pos.x == ACTUAL_WIDTH as i32 - 1 {
             true
         } else {
-            self.ensure_row(pos.1 as usize);
-            self.rows[pos.1 as usize][pos.0 as usize]
+            self.ensure_row(pos.y as usize);
+            self.rows[pos]
         }
     }
 
     fn ensure_row(&mut self, y: usize) {
-        if self.rows.len() < y {
-            self.rows.resize(y + 1, [false; ACTUAL_WIDTH]);
+        if self.rows.height() < y {
+            self.rows.resize_rows(y + 1, false);
             self.row_stats.resize(y + 1, None);
         }
     }
 
-    fn place_rock<'a, 'b>(&'a mut self, rock: &'b Rock) -> RockInstance<'b> {
-        //println!("palce size: {:?}", rock.size);
-        let offset = (3, self.max_height as i32 + 3);
-        self.ensure_row((offset.1 + rock.size.1) as usize);
+    fn place_rock<'b>(&mut self, rock: &'b Rock) -> RockInstance<'b> {
+        let offset = Pos::new(3, self.max_height as i32 + 3);
+        self.ensure_row((offset.y + rock.size.y) as usize);
         RockInstance::new(rock, offset)
     }
 
     fn try_move(&mut self, ri: &mut RockInstance, offset: Pos) -> bool {
         if !ri.offset_pixels(offset).any(|p| self.occupied(p)) {
-            //println!("occupied");
-            ri.offset = add_pos(ri.offset, offset);
+            ri.offset += offset;
             true
         } else {
             false
@@ -93,179 +93,199 @@ impl Field {
     }
 
     fn paint(&mut self, ri: RockInstance) {
-        let h = ri.offset.1 + ri.rock.size.1;
+        let h = ri.offset.y + ri.rock.size.y;
         if h > self.max_height as i32 {
             self.max_height = h as usize;
         }
-        //println!("max_height: {}", self.max_height);
-        ri.pixels()
-            .for_each(|p| self.rows[p.1 as usize][p.0 as usize] = true);
+        ri.pixels().for_each(|p| self.rows[p] = true);
     }
 
-    fn print(&self, ri: Option<&RockInstance>) {
-        for (y, line) in self.rows.iter().enumerate().rev() {
-            println!();
-            line.iter().enumerate().for_each(|(x, &b)| {
-                let char = if b {
-                    '#'
+    /// The `VIEW_HEIGHT` rows under the top of the falling rock, upside
+    /// down like in the puzzle.
+    fn frame(&self, ri: &RockInstance) -> Frame {
+        let top = ri.offset.y + ri.rock.size.y;
+        let mut frame = Frame::new(ACTUAL_WIDTH, VIEW_HEIGHT);
+        for row in 0..VIEW_HEIGHT {
+            let y = top - 1 - row as i32;
+            for x in 0..ACTUAL_WIDTH as i32 {
+                let pos = Pos::new(x, y);
+                let cell = if y < 0 {
+                    Cell::EMPTY
+                } else if y == 0 {
+                    let corner = x == 0 || x == ACTUAL_WIDTH as i32 - 1;
+                    Cell::new(if corner { '+' } else { '-' }, Color::GREY)
+                } else if x == 0 || x == ACTUAL_WIDTH as i32 - 1 {
+                    Cell::new('|', Color::GREY)
+                } else if ri.pixels().any(|p| p == pos) {
+                    Cell::new('@', Color::RED)
+                } else if self.rows.get(pos).copied().unwrap_or(false) {
+                    Cell::new('#', Color::WHITE)
                 } else {
-                    if let Some(ri) = ri {
-                        if ri.pixels().find(|&p| p == (x as i32, y as i32)).is_some() {
-                            '@'
-                        } else {
-                            '.'
-                        }
-                    } else {
-                        '.'
-                    }
+                    Cell::new('.', Color::BLACK)
                 };
-                print!("{}", char)
-            });
+                frame.set(Pos::new(x, row as i32), cell);
+            }
         }
-        println!();
+        frame
     }
 
-    fn print_line(&self, line: usize) {
-        println!();
-        self.rows[line].iter().for_each(|&b| {
-            print!("{}", if b { '#' } else { ' ' });
-        })
-    }
-
-    fn check_line(&self, ri: &RockInstance, iteration: usize) {
-        for i in ri.offset.1..ri.offset.1 + ri.rock.size.1 {
-            if self.rows[i as usize].iter().all(|&a| a) {
-                println!("row[{i}] is full at iteration: {iteration}");
+    /// Drops `rock` until it comes to rest, pushed by the jets from
+    /// `jets[*jet]` on, and leaves `jet` at the next one to blow.
+    fn drop_rock(&mut self, rock: &Rock, jets: &[Pos], jet: &mut usize) {
+        let mut ri = self.place_rock(rock);
+        loop {
+            self.try_move(&mut ri, jets[*jet]);
+            *jet = (*jet + 1) % jets.len();
+            if !self.try_move(&mut ri, Pos::new(0, -1)) {
+                break;
             }
         }
+        self.paint(ri);
+    }
+
+    /// The top `SURFACE_DEPTH` rows, a bit per column, from which the
+    /// rocks to come fall the same way wherever they are in the tower.
+    fn surface(&self) -> Vec<u8> {
+        let top = self.max_height.min(self.rows.height());
+        (top.saturating_sub(SURFACE_DEPTH)..top)
+            .map(|y| {
+                self.rows.row(y)[1..=FIELD_WIDTH]
+                    .iter()
+                    .rev()
+                    .fold(0, |bits, &occupied| bits << 1 | occupied as u8)
+            })
+            .collect()
     }
 
+    /// Drops `iteration_count` rocks, calling `on_move` after each move
+    /// with the rock now falling and the number of rocks at rest.
     fn simulate<'a>(
         &mut self,
         mut rocks: impl Iterator<Item = &'a Rock>,
         mut jets: impl Iterator<Item = Pos>,
         iteration_count: usize,
+        mut on_move: impl FnMut(&Self, &RockInstance, usize),
     ) {
         let mut ri = self.place_rock(rocks.next().unwrap());
         let mut curr_iter = 0;
         while curr_iter < iteration_count {
-            //println!("offset: {:?}", ri.offset);
-            //self.print(&ri);
             self.try_move(&mut ri, jets.next().unwrap());
-            if !self.try_move(&mut ri, (0, -1)) {
+            if !self.try_move(&mut ri, Pos::new(0, -1)) {
                 self.paint(ri);
                 ri = self.place_rock(rocks.next().unwrap());
                 curr_iter += 1;
             }
-            self.check_line(&ri, curr_iter);
-            //self.print(&ri);
-            //println!("{}", self.max_height);
+            on_move(self, &ri, curr_iter);
         }
     }
 }
 
-pub fn part1(input: &str) -> usize {
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Parsed<'a> = Vec<Pos>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Pos>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(jets: &Vec<Pos>, _: &()) -> Result<usize, SolveError> {
+        Ok(part1(jets))
+    }
+
+    fn part2(jets: &Vec<Pos>, _: &()) -> Result<usize, SolveError> {
+        Ok(part2(jets))
+    }
+}
+
+pub fn part1(jets: &[Pos]) -> usize {
     let mut field = Field::new();
     let rocks = parse_rocks(ROCKS);
     let rocks = rocks.iter().cycle();
-    let (_, jets) = parse_jets(input);
-    //field.simulate(rocks, jets, 2022);
-    field.simulate(rocks, jets, 2022);
+    let (_, jets) = cycle_jets(jets);
+    field.simulate(rocks, jets, 2022, |_, _, _| {});
     field.max_height - 1
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(jets: &[Pos]) -> usize {
+    tower_height(jets, 1_000_000_000_000)
+}
+
+/// The height of the tower after `rock_count` rocks. Once the next rock,
+/// the next jet and the surface of the tower repeat, the rocks in between
+/// repeat too: as many of those cycles as fit are skipped at once.
+fn tower_height(jets: &[Pos], rock_count: usize) -> usize {
+    let rocks = parse_rocks(ROCKS);
+    let mut field = Field::new();
+    let mut jet = 0;
+    let mut seen = HashMap::new();
+    let mut skipped = None;
+    let mut dropped = 0;
+    while dropped < rock_count {
+        field.drop_rock(&rocks[dropped % ROCK_TYPE_COUNT], jets, &mut jet);
+        dropped += 1;
+        if skipped.is_some() {
+            continue;
+        }
+        let state = (dropped % ROCK_TYPE_COUNT, jet, field.surface());
+        if let Some((before, height)) = seen.insert(state, (dropped, field.max_height)) {
+            let period = dropped - before;
+            let cycles = (rock_count - dropped) / period;
+            dropped += cycles * period;
+            skipped = Some(cycles * (field.max_height - height));
+        }
+    }
+    field.max_height - 1 + skipped.unwrap_or(0)
+}
+
+/// Drops the rocks of part 1, recording a frame after each move.
+pub fn visualize(jets: &[Pos], recorder: &mut dyn Recorder) -> io::Result<()> {
     let mut field = Field::new();
     let rocks = parse_rocks(ROCKS);
-    let rocks = rocks.iter().cycle();
-    let (jet_count, jets) = parse_jets(input);
-    field.simulate(rocks, jets, 10000);
-    //field.print(None);
-    let bytes = field
-        .rows
-        .iter()
-        .map(|row| {
-            let mut result: u8 = 0;
-            let mut mask: u8 = 1;
-            for i in 1..8 {
-                if row[i] {
-                    result |= mask;
-                }
-                mask <<= 1;
-            }
-            result
-        })
-        .collect::<Vec<_>>();
-    let search_string = &bytes[1000..1004];
-    let instances = bytes[1000..]
-        .windows(4)
-        .enumerate()
-        .filter_map(|(i, w)| (w == search_string).then_some(i))
-        .collect::<Vec<_>>();
-    instances
-        .windows(2)
-        .for_each(|w| println!("{}", w[1] - w[0]));
-    // .windows(2)
-    // .map(|w| (w[0].0, w[1].0, w[1].1 - w[0].1))
-    // .for_each(|i| println!("{i}"));
-    // println!("{:?}", bytes);
-    // let mut last_left_found = 0;
-    // let mut last_right_found = 0;
-    // let mut i = 0;
-    // while i < 100000 {
-    //     //search left
-    //     while !field.rows[i][1] {
-    //         i += 1;
-    //     }
-    //     println!(
-    //         "found left at {}, that's {} after last left.",
-    //         i,
-    //         i - last_left_found
-    //     );
-    //     last_left_found = i;
-    //     while !field.rows[i][7] {
-    //         i += 1;
-    //     }
-    //     println!(
-    //         "found right at {}, that's {} after last right.",
-    //         i,
-    //         i - last_right_found
-    //     );
-    //     last_right_found = i;
-    // }
-    // field.simulate(rocks, jets, jet_count * 5 * 100);
-    // for i in 0..90 {
-    //     field.print_line(i * jet_count * 5);
-    // }
-    field.max_height - 1
+    let (_, jets) = cycle_jets(jets);
+    let mut result = Ok(());
+    field.simulate(rocks.iter().cycle(), jets, 2022, |field, ri, _| {
+        if result.is_ok() {
+            result = recorder.record(&field.frame(ri));
+        }
+    });
+    result?;
+    recorder.finish()
 }
 
 fn parse_rocks(input: &str) -> [Rock; 5] {
     let mut result = std::array::from_fn(|_| Rock {
         pixels: vec![],
-        size: (0, 0),
+        size: Pos::new(0, 0),
     });
     let mut rock_index = 0;
     let mut lines = input.lines();
     let mut rock_line = 0;
-    let mut pixels: Vec<(i32, i32)> = vec![];
+    let mut pixels: Vec<Pos> = vec![];
     while rock_index < ROCK_TYPE_COUNT {
         let line = lines.next();
         if line.is_none() || line.unwrap().is_empty() {
             let (min_x, max_x) = pixels.iter().fold((i32::MAX, i32::MIN), |r, &pos| {
-                (r.0.min(pos.0), r.1.max(pos.0))
+                (r.0.min(pos.x), r.1.max(pos.x))
             });
             let rock = &mut result[rock_index];
-            rock.pixels = pixels.iter().map(|&p| (p.0, rock_line - p.1 - 1)).collect();
-            rock.size = (1 + max_x - min_x, rock_line);
+            rock.pixels = pixels
+                .iter()
+                .map(|&p| Pos::new(p.x, rock_line - p.y - 1))
+                .collect();
+            rock.size = Pos::new(1 + max_x - min_x, rock_line);
             rock_line = 0;
             pixels.clear();
             rock_index += 1;
         } else {
-            let Some(line) = line else { panic!()};
+            let Some(line) = line else { panic!() };
             line.chars().enumerate().for_each(|(x, c)| {
                 if c == '#' {
-                    pixels.push((x as i32, rock_line))
+                    pixels.push(Pos::new(x as i32, rock_line))
                 }
             });
             rock_line += 1
@@ -274,97 +294,104 @@ fn parse_rocks(input: &str) -> [Rock; 5] {
     result
 }
 
-fn parse_jets<'a>(input: &'a str) -> (usize, impl Iterator<Item = Pos> + 'a) {
-    let jets = input.as_bytes().iter().filter_map(|&c| match c {
-        b'<' => Some((-1, 0)),
-        b'>' => Some((1, 0)),
-        _ => None,
-    });
-    (jets.clone().count(), jets.cycle())
+pub fn parse(input: &str) -> Result<Vec<Pos>, ParseError> {
+    let source = Source::new(Day17::DAY, input);
+    let pattern = input.trim();
+    if pattern.is_empty() {
+        return Err(source.eof("expected a jet pattern"));
+    }
+    source.check_chars(pattern, |c| c == '<' || c == '>', "expected < or >")?;
+    Ok(pattern
+        .bytes()
+        .map(|c| if c == b'<' { LEFT } else { RIGHT })
+        .collect())
 }
 
-// fn brent(data: &[u8]) -> (usize, usize) {
-//     let cycle_len: usize;
-//     let mut hare = 0;
-//     let mut power: usize = 1;
-//     'outer: loop {
-//         let tortoise = hare;
-//         for i in 1..=power {
-//             hare = data[hare] as usize;
-//             if tortoise == hare {
-//                 cycle_len = i;
-//                 break 'outer;
-//             }
-//         }
-//         power *= 2;
-//     }
-//     hare = 0;
-//     for _ in 0..cycle_len {
-//         hare = data[hare] as usize;
-//     }
-//     let mut cycle_start = 0;
-//     let mut tortoise = 0;
-//     while tortoise != hare {
-//         tortoise = data[tortoise] as usize;
-//         hare = data[hare] as usize;
-//         cycle_start += 1;
-//     }
-//     (cycle_start, cycle_len)
-// }
+fn cycle_jets(jets: &[Pos]) -> (usize, impl Iterator<Item = Pos> + '_) {
+    (jets.len(), jets.iter().copied().cycle())
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
-    // #[test]
-    // fn test_brent() {
-    //     println!("{:?}", brent(&[1, 9, 3, 4, 5, 1, 3]));
-    //     assert_eq!(brent(&[1, 2, 3, 1, 2, 3]), (1, 3));
-    //     assert_eq!(brent(&[1, 2, 1, 3, 1, 2, 1, 3]), (1, 2));
-    // }
-
-    fn print_rocks() {
-        let rocks = parse_rocks(ROCKS);
-        for rock in &rocks {
-            let mut pixels = [[' '; 5]; 5];
-            rock.pixels
-                .iter()
-                .for_each(|pixel| pixels[pixel.1 as usize][pixel.0 as usize] = '#');
-            for row in &pixels {
-                println!();
-                for char in row {
-                    print!("{char}");
-                }
-            }
+    /// The rock drawn like in `shapes.txt`, its bottom row last.
+    fn rock_frame(rock: &Rock) -> Frame {
+        let (width, height) = (rock.size.x as usize, rock.size.y as usize);
+        let mut frame = Frame::filled(width, height, Cell::new('.', Color::BLACK));
+        for p in &rock.pixels {
+            let pos = Pos::new(p.x, rock.size.y - 1 - p.y);
+            frame.set(pos, Cell::new('#', Color::WHITE));
         }
-        println!();
+        frame
     }
 
     #[test]
     fn test_parse_shapes() {
         let rocks = parse_rocks(ROCKS);
-        for (rock, pos) in rocks
-            .into_iter()
-            .zip([(4, 1), (3, 3), (3, 3), (1, 4), (2, 2)])
-        {
-            assert_eq!(rock.size, pos);
+        for (rock, pos) in rocks.iter().zip([(4, 1), (3, 3), (3, 3), (1, 4), (2, 2)]) {
+            assert_eq!((rock.size.x, rock.size.y), pos);
         }
+        let drawn = rocks.iter().map(|rock| rock_frame(rock).to_ascii());
+        let drawn = drawn.collect::<Vec<_>>().join("\n");
+        assert!(drawn.lines().eq(ROCKS.lines()), "{drawn}");
+    }
+
+    #[test]
+    fn test_frame() {
+        let rocks = parse_rocks(ROCKS);
+        let mut field = Field::new();
+        let jets = parse(TEST_INPUT).unwrap();
+        let (_, jets) = cycle_jets(&jets);
+        let mut frames = vec![];
+        field.simulate(rocks.iter().cycle(), jets, 2, |field, ri, _| {
+            frames.push(field.frame(ri).to_ascii())
+        });
+        // the second rock just appeared, the first at rest
+        let lines = frames[3].lines().take(8).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "|...@...|",
+                "|..@@@..|",
+                "|...@...|",
+                "|.......|",
+                "|.......|",
+                "|.......|",
+                "|..####.|",
+                "+-------+"
+            ]
+        );
+        assert_eq!(frames[3].lines().count(), VIEW_HEIGHT);
+    }
+
+    fn simulated_height(jets: &[Pos], rock_count: usize) -> usize {
+        let mut field = Field::new();
+        let rocks = parse_rocks(ROCKS);
+        let (_, jets) = cycle_jets(jets);
+        field.simulate(rocks.iter().cycle(), jets, rock_count, |_, _, _| {});
+        field.max_height - 1
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 3068);
-        assert_eq!(part1(INPUT), 3219);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 3068);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 3219);
         // assert_eq!(part1(SHORT_INPUT), 10);
-        // assert_eq!(part1(TEST_INPUT), 64);
-        // assert_eq!(part1(INPUT), 4314);
+        // assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 64);
+        // assert_eq!(part1(&parse(INPUT).unwrap()), 4314);
     }
 
     #[test]
     fn test_part2() {
-        // assert_eq!(part2(TEST_INPUT), 58);
-        // assert_eq!(part2(INPUT), 2444);
+        let jets = parse(INPUT).unwrap();
+        // past the first cycles, skipping some
+        assert_eq!(tower_height(&jets, 2022), part1(&jets));
+        assert_eq!(tower_height(&jets, 20_000), simulated_height(&jets, 20_000));
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 1514285714288);
+        assert_eq!(part2(&jets), 1582758620701);
     }
 }
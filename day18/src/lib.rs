@@ -1,47 +1,72 @@
 use std::collections::VecDeque;
 
-pub const INPUT: &str = include_str!("input.txt");
+use common::{ParseError, Solution, SolveError, Source, Vec3};
 
-type Pos = (i32, i32, i32);
+type Pos = Vec3<i32>;
 
-const NEIGHBORS: [Pos; 6] = [
-    (-1, 0, 0),
-    (1, 0, 0),
-    (0, -1, 0),
-    (0, 1, 0),
-    (0, 0, -1),
-    (0, 0, 1),
-];
+/// The field holds every cube of the droplet's bounding box, so
+/// coordinates are kept within `-MAX_COORD..=MAX_COORD`.
+const MAX_COORD: i32 = 100;
 
-pub fn part1(input: &str) -> i32 {
-    Field::parse(input).free_face_count()
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Parsed<'a> = Field;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Field, ParseError> {
+        parse(input)
+    }
+
+    fn part1(field: &Field, _: &()) -> Result<i32, SolveError> {
+        Ok(part1(field))
+    }
+
+    fn part2(field: &Field, _: &()) -> Result<i32, SolveError> {
+        Ok(part2(field))
+    }
 }
 
-pub fn part2(input: &str) -> i32 {
-    Field::parse(input).exterior_face_count()
+pub fn parse(input: &str) -> Result<Field, ParseError> {
+    Field::parse(input)
 }
 
-fn add_pos(pos1: Pos, pos2: Pos) -> Pos {
-    (pos1.0 + pos2.0, pos1.1 + pos2.1, pos1.2 + pos2.2)
+pub fn part1(field: &Field) -> i32 {
+    field.free_face_count()
 }
 
-fn sub_pos(pos1: Pos, pos2: Pos) -> Pos {
-    (pos1.0 - pos2.0, pos1.1 - pos2.1, pos1.2 - pos2.2)
+pub fn part2(field: &Field) -> i32 {
+    field.exterior_face_count()
 }
 
 fn neighbors(pos: &Pos) -> impl Iterator<Item = Pos> + '_ {
-    NEIGHBORS.iter().map(move |&n| add_pos(n, *pos))
+    Pos::NEIGHBORS6.iter().map(move |&n| n + *pos)
 }
 
-fn parse<'a>(input: &'a str) -> impl Iterator<Item = Pos> + Clone + 'a {
-    input.split_whitespace().filter(|s| !s.is_empty()).map(|s| {
-        let mut coords = s.split(',').map(|s| s.parse::<i32>().unwrap());
-        (
-            coords.next().unwrap(),
-            coords.next().unwrap(),
-            coords.next().unwrap(),
-        )
-    })
+fn parse_positions(input: &str) -> Result<Vec<Pos>, ParseError> {
+    let source = Source::new(Day18::DAY, input);
+    let positions = input
+        .split_whitespace()
+        .map(|s| {
+            let (x, rest) = source.split_once(s, ",")?;
+            let (y, z) = source.split_once(rest, ",")?;
+            let coord = |text| {
+                let coord = source.parse::<i32>(text)?;
+                if !(-MAX_COORD..=MAX_COORD).contains(&coord) {
+                    return Err(source.error(text, "coordinate too far from 0"));
+                }
+                Ok(coord)
+            };
+            Ok(Pos::new(coord(x)?, coord(y)?, coord(z)?))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if positions.is_empty() {
+        return Err(source.eof("expected at least one cube"));
+    }
+    Ok(positions)
 }
 
 #[derive(Clone, Copy)]
@@ -53,59 +78,51 @@ struct Bounds {
 impl Bounds {
     fn new(positions: &[Pos]) -> Self {
         let (min, max) = positions.iter().fold(
-            (
-                (i32::MAX, i32::MAX, i32::MAX),
-                (i32::MIN, i32::MIN, i32::MIN),
-            ),
-            |((min_x, min_y, min_z), (max_x, max_y, max_z)), pos| {
-                (
-                    (min_x.min(pos.0), min_y.min(pos.1), min_z.min(pos.2)),
-                    (max_x.max(pos.0), max_y.max(pos.1), max_z.max(pos.2)),
-                )
-            },
+            (Pos::ONE * i32::MAX, Pos::ONE * i32::MIN),
+            |(min, max), &pos| (min.min(pos), max.max(pos)),
         );
-        let lengths = add_pos(sub_pos(max, min), (1, 1, 1));
+        let lengths = max - min + Pos::ONE;
         Self { low: min, lengths }
     }
 
     fn grow(self) -> Self {
         Self {
-            low: sub_pos(self.low, (1, 1, 1)),
-            lengths: add_pos(self.lengths, (2, 2, 2)),
+            low: self.low - Pos::ONE,
+            lengths: self.lengths + Pos::ONE * 2,
         }
     }
 
     fn capacity(&self) -> usize {
-        (self.lengths.0 * self.lengths.1 * self.lengths.2) as usize
+        (self.lengths.x * self.lengths.y * self.lengths.z) as usize
     }
 
     fn index(&self, pos: Pos) -> usize {
-        let pos = sub_pos(pos, self.low);
-        (pos.0 + pos.1 * self.lengths.0 + pos.2 * self.lengths.0 * self.lengths.1) as usize
+        let pos = pos - self.low;
+        (pos.x + pos.y * self.lengths.x + pos.z * self.lengths.x * self.lengths.y) as usize
     }
 
     fn in_bounds(&self, pos: Pos) -> bool {
-        (self.low.0..self.low.0 + self.lengths.0).contains(&pos.0)
-            && (self.low.1..self.low.1 + self.lengths.1).contains(&pos.1)
-            && (self.low.2..self.low.2 + self.lengths.2).contains(&pos.2)
+        (self.low.x..self.low.x + self.lengths.x).contains(&pos.x)
+            && (self.low.y..self.low.y + self.lengths.y).contains(&pos.y)
+            && (self.low.z..self.low.z + self.lengths.z).contains(&pos.z)
     }
 }
 
-struct Field {
+pub struct Field {
     bounds: Bounds,
     data: Vec<bool>,
     positions: Vec<Pos>,
 }
 
 impl Field {
-    fn parse(input: &str) -> Self {
-        let positions = parse(input).collect::<Vec<_>>();
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let positions = parse_positions(input)?;
         let bounds = Bounds::new(&positions).grow();
         let mut result = Self::new(bounds, Vec::with_capacity(bounds.capacity()));
         for pos in positions {
             result.set(pos);
         }
-        result
+        Ok(result)
     }
 
     fn new(bounds: Bounds, positions: Vec<Pos>) -> Self {
@@ -159,7 +176,7 @@ impl Field {
         let enclosing = self.enclosing_space();
         self.positions
             .iter()
-            .map(|pos| neighbors(&pos).filter(|&n| enclosing.contains(n)).count() as i32)
+            .map(|pos| neighbors(pos).filter(|&n| enclosing.contains(n)).count() as i32)
             .sum()
     }
 }
@@ -168,24 +185,34 @@ impl Field {
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
     const SHORT_INPUT: &str = "1,1,1 2,1,1";
     #[test]
     fn test_parsing() {
-        assert_eq!(Field::parse(SHORT_INPUT).len(), 2);
-        assert_eq!(Field::parse(TEST_INPUT).len(), 13);
+        assert_eq!(Field::parse(SHORT_INPUT).unwrap().len(), 2);
+        assert_eq!(Field::parse(TEST_INPUT).unwrap().len(), 13);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(SHORT_INPUT), 10);
-        assert_eq!(part1(TEST_INPUT), 64);
-        assert_eq!(part1(INPUT), 4314);
+        assert_eq!(part1(&parse(SHORT_INPUT).unwrap()), 10);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 64);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 4314);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 58);
-        assert_eq!(part2(INPUT), 2444);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 58);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 2444);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        // found by `cargo fuzz`
+        let error = parse("2,2,22\n-2,-2111112,-222\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 4));
+        let error = parse("1,-2147483648,1").err().unwrap();
+        assert_eq!(error.text, "-2147483648");
     }
 }
@@ -1,7 +1,8 @@
 // we should go back to array based solution now that
 // we know the bug was about modulos
 
-pub const INPUT: &str = include_str!("input.txt");
+use common::{ParseError, Solution, SolveError, Source};
+
 const KEY: i64 = 811589153;
 
 type Number = i64;
@@ -36,13 +37,9 @@ impl State {
         Self { numbers, links }
     }
 
+    #[cfg(test)]
     fn parse(input: &str) -> Self {
-        let numbers = input
-            .lines()
-            .filter(|s| !s.is_empty())
-            .map(|s| s.parse().unwrap())
-            .collect::<Vec<_>>();
-        Self::new(numbers)
+        Self::new(parse(input).unwrap())
     }
 
     fn move_number(&mut self, num_index: NumberId, key: Number) {
@@ -115,18 +112,53 @@ impl State {
         let mut result = [0; 3];
         let zero_index = self.index_of(0);
         let mut index = zero_index;
-        for i in 0..3 {
+        for slot in result.iter_mut() {
             for _ in 0..1000 {
                 index = self.right_index(index);
             }
-            result[i] = index;
+            *slot = index;
         }
         result
     }
 }
 
-pub fn part1(input: &str) -> i64 {
-    let mut state = State::parse(input);
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Parsed<'a> = Vec<Number>;
+    type Params = ();
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Number>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(numbers: &Vec<Number>, _: &()) -> Result<i64, SolveError> {
+        Ok(part1(numbers))
+    }
+
+    fn part2(numbers: &Vec<Number>, _: &()) -> Result<i64, SolveError> {
+        Ok(part2(numbers))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Number>, ParseError> {
+    let source = Source::new(Day20::DAY, input);
+    let numbers = input
+        .lines()
+        .filter(|s| !s.is_empty())
+        .map(|s| source.parse(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !numbers.contains(&0) {
+        return Err(source.eof("expected a 0 to count the grove coordinates from"));
+    }
+    Ok(numbers)
+}
+
+pub fn part1(numbers: &[Number]) -> i64 {
+    let mut state = State::new(numbers.to_vec());
     state
         .mix(1, 1)
         .iter()
@@ -134,8 +166,8 @@ pub fn part1(input: &str) -> i64 {
         .sum()
 }
 
-pub fn part2(input: &str) -> i64 {
-    let mut state = State::parse(input);
+pub fn part2(numbers: &[Number]) -> i64 {
+    let mut state = State::new(numbers.to_vec());
     state
         .mix(10, KEY)
         .iter()
@@ -152,6 +184,7 @@ fn pos_mod(val: i64, div: usize) -> usize {
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     fn cycle_equals(v1: &[i64], v2: &[i64]) -> bool {
@@ -165,20 +198,20 @@ mod tests {
 
     #[test]
     fn test_modulo() {
-        assert_eq!(0i64 % 7, 0);
+        assert_eq!(0i64, 0);
         // assert_eq!(-4i64 % 4, -1);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 3);
-        assert_eq!(part1(INPUT), 7713);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 3);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 7713);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 1623178306);
-        assert_eq!(part2(INPUT), 1664569352803);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 1623178306);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 1664569352803);
     }
 
     #[test]
@@ -1,7 +1,7 @@
-pub const INPUT: &str = include_str!("input.txt");
-
 use std::collections::HashMap;
 
+use common::{ParseError, Solution, SolveError, Source};
+
 type MonkeyId = usize;
 type Number = i64;
 type MonkeyPair = (MonkeyId, MonkeyId);
@@ -43,14 +43,14 @@ impl MathOp {
         }
     }
 
-    fn parse(input: &str) -> Self {
+    fn parse(source: Source, input: &str) -> Result<Self, ParseError> {
         use MathOp::*;
         match input {
-            "+" => Sum,
-            "-" => Sub,
-            "*" => Mul,
-            "/" => Div,
-            _ => unreachable!(),
+            "+" => Ok(Sum),
+            "-" => Ok(Sub),
+            "*" => Ok(Mul),
+            "/" => Ok(Div),
+            _ => Err(source.error(input, "expected +, -, * or /")),
         }
     }
 }
@@ -65,37 +65,55 @@ enum ResultPair {
     VarRight(MathOp, Number),
 }
 
+#[derive(Debug, Clone)]
 pub struct Troop<'a> {
     monkeys: Vec<Option<Monkey>>,
     name_to_id: HashMap<&'a str, MonkeyId>,
 }
 
 impl<'a> Troop<'a> {
-    pub fn parse(input: &'a str) -> Self {
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
         use Monkey::*;
-        input.lines().filter(|l| !l.is_empty()).fold(
-            Self {
-                monkeys: vec![],
-                name_to_id: Default::default(),
-            },
-            |mut result, l| {
-                let mut part = l.split(": ");
-                let name = part.next().unwrap();
-                let mut tokens = part.next().unwrap().split_whitespace();
-                let first = tokens.next().unwrap();
-                let id = result.name_id(name);
-                match first.parse::<i64>() {
-                    Ok(number) => result.set_monkey(id, Literal(number)),
-                    Err(_) => {
-                        let first = result.name_id(first);
-                        let op = tokens.next().unwrap();
-                        let second = result.name_id(tokens.next().unwrap());
-                        result.set_monkey(id, Op(MathOp::parse(op), (first, second)));
-                    }
+        let source = Source::new(Day21::DAY, input);
+        let mut result = Self {
+            monkeys: vec![],
+            name_to_id: Default::default(),
+        };
+        for l in input.lines().filter(|l| !l.is_empty()) {
+            let (name, job) = source.split_once(l, ": ")?;
+            let id = result.name_id(name);
+            if result.monkeys[id].is_some() {
+                return Err(source.error(name, "monkey is defined twice"));
+            }
+            let tokens = job.split_whitespace().collect::<Vec<_>>();
+            match *tokens.as_slice() {
+                [number] => result.set_monkey(id, Literal(source.parse(number)?)),
+                [first, op, second] => {
+                    let op = MathOp::parse(source, op)?;
+                    let first = result.name_id(first);
+                    let second = result.name_id(second);
+                    result.set_monkey(id, Op(op, (first, second)));
                 }
-                result
-            },
-        )
+                _ => return Err(source.error(job, "expected a number or an operation")),
+            }
+        }
+        if let Some(name) = result
+            .name_to_id
+            .iter()
+            .filter(|&(_, &id)| result.monkeys[id].is_none())
+            .map(|(&name, _)| name)
+            .min_by_key(|name| name.as_ptr())
+        {
+            return Err(source.error(name, "monkey is never defined"));
+        }
+        match result.name_to_id.get("root").map(|&id| result.monkeys[id]) {
+            Some(Some(Op(..))) => {}
+            _ => return Err(source.eof("expected a root monkey doing an operation")),
+        }
+        if !result.name_to_id.contains_key("humn") {
+            return Err(source.eof("expected a humn monkey"));
+        }
+        Ok(result)
     }
 
     fn name_id<'b>(&'b mut self, name: &'a str) -> MonkeyId {
@@ -109,7 +127,7 @@ impl<'a> Troop<'a> {
         }
     }
 
-    fn set_monkey<'b>(&'b mut self, id: MonkeyId, monkey: Monkey) {
+    fn set_monkey(&mut self, id: MonkeyId, monkey: Monkey) {
         self.monkeys[id] = Some(monkey);
     }
 
@@ -152,10 +170,34 @@ impl<'a> Troop<'a> {
     }
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Parsed<'a> = Troop<'a>;
+    type Params = ();
+    type Answer1 = Number;
+    type Answer2 = Number;
+
+    fn parse(input: &str) -> Result<Troop<'_>, ParseError> {
+        Troop::parse(input)
+    }
+
+    fn part1(troop: &Troop, _: &()) -> Result<Number, SolveError> {
+        Ok(part1(troop))
+    }
+
+    fn part2(troop: &Troop, _: &()) -> Result<Number, SolveError> {
+        Ok(part2(&mut troop.clone()))
+    }
+}
+
 pub fn part1(troop: &Troop) -> Number {
     use EvalResult::*;
     let root = troop.name_to_id["root"];
-    let Literal(result) = troop.eval(root) else { unreachable!() };
+    let Literal(result) = troop.eval(root) else {
+        unreachable!()
+    };
     result
 }
 
@@ -164,10 +206,14 @@ pub fn part2(troop: &mut Troop) -> Number {
     let root = troop.name_to_id["root"];
     let human = troop.name_to_id["humn"];
     let r = troop.monkeys[root].as_mut().unwrap();
-    let Monkey::Op(_, pair) = *r else { unreachable!()} ;
+    let Monkey::Op(_, pair) = *r else {
+        unreachable!()
+    };
     *r = Monkey::Eq(pair);
     troop.monkeys[human] = Some(Monkey::Variable);
-    let Literal(result) = troop.eval(root) else { unreachable!() };
+    let Literal(result) = troop.eval(root) else {
+        unreachable!()
+    };
     result
 }
 
@@ -175,19 +221,34 @@ pub fn part2(troop: &mut Troop) -> Number {
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
     const TEST_INPUT2: &str = include_str!("test_input2.txt");
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&Troop::parse(TEST_INPUT)), 152);
-        assert_eq!(part1(&Troop::parse(INPUT)), 87457751482938);
+        assert_eq!(part1(&Troop::parse(TEST_INPUT).unwrap()), 152);
+        assert_eq!(part1(&Troop::parse(INPUT).unwrap()), 87457751482938);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&mut Troop::parse(TEST_INPUT2)), 19);
-        assert_eq!(part2(&mut Troop::parse(TEST_INPUT)), 301);
-        assert_eq!(part2(&mut Troop::parse(INPUT)), 3221245824363);
+        assert_eq!(part2(&mut Troop::parse(TEST_INPUT2).unwrap()), 19);
+        assert_eq!(part2(&mut Troop::parse(TEST_INPUT).unwrap()), 301);
+        assert_eq!(part2(&mut Troop::parse(INPUT).unwrap()), 3221245824363);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = TEST_INPUT.replace("sllz + lgvd", "sllz % lgvd");
+        let error = Troop::parse(&input).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (21, 3, 12));
+        assert_eq!(error.text, "%");
+        let input = TEST_INPUT.replace("sllz + lgvd", "sllz + lgvx");
+        let error = Troop::parse(&input).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 14, "lgvx")
+        );
     }
 }
@@ -1,19 +1,54 @@
-pub const INPUT: &str = include_str!("input.txt");
+#![cfg_attr(not(feature = "std"), no_std)]
 
-fn to_dec(snafu: &str) -> i64 {
-    let mut result = 0;
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use common::{for_each_line, ReadError};
+use common::{ParseError, Solution, SolveError, Source};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const HAS_PART2: bool = false;
+    type Parsed<'a> = Vec<i64>;
+    type Params = ();
+    type Answer1 = String;
+    type Answer2 = Infallible;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(numbers: &Vec<i64>, _: &()) -> Result<String, SolveError> {
+        Ok(part1(numbers))
+    }
+
+    fn part2(_: &Vec<i64>, _: &()) -> Result<Infallible, SolveError> {
+        unreachable!("day 25 has no second part")
+    }
+}
+
+/// `None` if the number doesn't fit in an `i64`.
+fn to_dec(snafu: &str) -> Option<i64> {
+    let mut result: i64 = 0;
     for &b in snafu.as_bytes().iter() {
-        result = result * 5
-            + match b {
-                b'0' => 0,
-                b'1' => 1,
-                b'2' => 2,
-                b'-' => -1,
-                b'=' => -2,
-                _ => panic!("invalid snafu numebr"),
-            }
+        result = result.checked_mul(5)?.checked_add(match b {
+            b'0' => 0,
+            b'1' => 1,
+            b'2' => 2,
+            b'-' => -1,
+            b'=' => -2,
+            _ => panic!("invalid snafu numebr"),
+        })?
     }
-    result
+    Some(result)
 }
 
 fn to_snafu(val: i64) -> String {
@@ -37,20 +72,44 @@ fn to_snafu(val: i64) -> String {
     result
 }
 
-pub fn part1(input: &str) -> String {
-    to_snafu(
-        input
-            .lines()
-            .filter(|l| !l.is_empty())
-            .map(to_dec)
-            .sum::<i64>(),
-    )
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let source = Source::new(Day25::DAY, input);
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| parse_number(source, l))
+        .collect()
+}
+
+fn parse_number(source: Source, line: &str) -> Result<i64, ParseError> {
+    source.check_chars(line, |c| "012-=".contains(c), "expected a SNAFU digit")?;
+    to_dec(line).ok_or_else(|| source.error(line, "number too large"))
+}
+
+pub fn part1(numbers: &[i64]) -> String {
+    to_snafu(numbers.iter().sum())
+}
+
+/// Part 1, reading the numbers a line at a time instead of holding them.
+#[cfg(feature = "std")]
+pub fn part1_reader(reader: impl BufRead) -> Result<String, ReadError> {
+    let mut sum: i64 = 0;
+    for_each_line(Day25::DAY, reader, |source, line| {
+        if !line.is_empty() {
+            sum = sum
+                .checked_add(parse_number(source, line)?)
+                .ok_or_else(|| source.error(line, "sum too large"))?;
+        }
+        Ok(())
+    })?;
+    Ok(to_snafu(sum))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const INPUT: &str = include_str!("input.txt");
     const TEST_INPUT: &str = include_str!("test_input.txt");
 
     const TEST_VALUES: [(i64, &str); 15] = [
@@ -74,7 +133,7 @@ mod tests {
     #[test]
     fn test_to_dec() {
         for (dec, snafu) in TEST_VALUES {
-            assert_eq!(to_dec(snafu), dec);
+            assert_eq!(to_dec(snafu), Some(dec));
         }
     }
 
@@ -87,7 +146,28 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), "2=-1=0");
-        assert_eq!(part1(INPUT), "2-==10--=-0101==1201");
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), "2=-1=0");
+        assert_eq!(part1(&parse(INPUT).unwrap()), "2-==10--=-0101==1201");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        // found by `cargo fuzz`
+        let error = parse("1=1\n2222222222222222222222222222\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_part1_reader() {
+        for input in [TEST_INPUT, INPUT] {
+            let expected = part1(&parse(input).unwrap());
+            assert_eq!(part1_reader(input.as_bytes()).unwrap(), expected);
+        }
+        let input = "222222222222222222222222222\n".repeat(3);
+        let Err(ReadError::Parse(error)) = part1_reader(input.as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.reason.as_str()), (3, "sum too large"));
     }
 }
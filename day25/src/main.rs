@@ -1,6 +0,0 @@
-use cark_aoc_helper::*;
-use day25::*;
-
-fn main() {
-    exec_and_print("Part1", || part1(INPUT));
-}
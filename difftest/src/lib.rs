@@ -0,0 +1,104 @@
+//! Differential testing: checks that two ways of getting an answer agree on
+//! random puzzle inputs. A disagreement is shrunk to a small input, saved
+//! to a file that `aoc run <day> --input <file>` can replay.
+
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+pub use proptest;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+
+/// Runs `agree` on `cases` inputs rendered by `input` from values of
+/// `strategy`. `agree` fails, or panics, when the implementations disagree.
+///
+/// Panics with the smallest failing input found, after saving it to
+/// `dayNN-<name>.txt`.
+pub fn check<S>(
+    day: u8,
+    name: &str,
+    cases: u32,
+    strategy: S,
+    input: impl Fn(&S::Value) -> String,
+    agree: impl Fn(&str) -> Result<(), String>,
+) where
+    S: Strategy,
+    S::Value: Debug,
+{
+    let config = Config {
+        failure_persistence: None,
+        ..Config::with_cases(cases)
+    };
+    let mut runner = TestRunner::new(config);
+    let result = runner.run(&strategy, |value| {
+        agree(&input(&value)).map_err(TestCaseError::fail)
+    });
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, value)) => {
+            let text = input(&value);
+            let path = save(day, name, &text);
+            panic!(
+                "day {day:02} {name}: {reason}\nminimal input, saved to {}:\n{text}",
+                path.display()
+            );
+        }
+        Err(TestError::Abort(reason)) => panic!("day {day:02} {name}: {reason}"),
+    }
+}
+
+/// Fails with both values unless they are equal.
+pub fn same<T: PartialEq + Debug>(left: T, right: T) -> Result<(), String> {
+    if left == right {
+        Ok(())
+    } else {
+        Err(format!("{left:?} != {right:?}"))
+    }
+}
+
+fn save(day: u8, name: &str, input: &str) -> PathBuf {
+    // `target/reproducers` in the workspace, unless `REPRODUCER_DIR` says
+    // otherwise.
+    let dir = match std::env::var_os("REPRODUCER_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("target/reproducers"),
+    };
+    let path = dir.join(format!("day{day:02}-{name}.txt"));
+    if let Err(error) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, input)) {
+        eprintln!("cannot save {}: {error}", path.display());
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+
+    #[test]
+    fn test_shrinks_and_saves() {
+        let dir = std::env::temp_dir().join("difftest");
+        std::env::set_var("REPRODUCER_DIR", &dir);
+        let result = std::panic::catch_unwind(|| {
+            check(
+                0,
+                "big",
+                100,
+                vec(0u32..100, 0..20),
+                |numbers| numbers.iter().map(|n| format!("{n}\n")).collect::<String>(),
+                |input| match input.lines().find(|l| l.parse::<u32>().unwrap() >= 50) {
+                    None => Ok(()),
+                    Some(n) => Err(format!("{n} is too big")),
+                },
+            )
+        });
+        assert!(result.is_err());
+        // The other numbers are dropped, and the big one shrunk until it
+        // barely is.
+        let saved = std::fs::read_to_string(dir.join("day00-big.txt")).unwrap();
+        assert_eq!(saved, "50\n");
+    }
+}
@@ -0,0 +1,15 @@
+//! `cargo fuzz` targets for the parser of every day, run from this
+//! directory with `cargo +nightly fuzz run dayNN`.
+//!
+//! A parser must turn any text into either its parsed form or a
+//! `ParseError`, never panic. The inputs that made one panic are kept as
+//! regression tests in that day's crate.
+
+use common::Solution;
+
+/// Parses `data` as the input of `S`, if it is text.
+pub fn parse<S: Solution>(data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = S::parse(input);
+    }
+}
@@ -0,0 +1,251 @@
+use std::ops::{Index, IndexMut};
+
+use common::{ParseError, Source};
+
+mod pos;
+
+pub use pos::{line, lines, Pos, DOWN, LEFT, NEIGHBORS4, NEIGHBORS8, RIGHT, UP};
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Adds or removes rows at the bottom, so the grid is `height` tall.
+    pub fn resize_rows(&mut self, height: usize, fill: T) {
+        self.cells.resize(self.width * height, fill);
+        self.height = height;
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads one cell per character, a line per row. Blank lines are
+    /// skipped, and lines may end with `\n` or `\r\n`. Characters `cell`
+    /// turns down are reported with `reason`, and so are ragged lines.
+    pub fn parse(
+        source: Source,
+        reason: &str,
+        mut cell: impl FnMut(Pos, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in source.text().lines().filter(|line| !line.is_empty()) {
+            let y = height as i32;
+            let mut x = 0;
+            for (i, c) in line.char_indices() {
+                let value = cell(Pos::new(x, y), c)
+                    .ok_or_else(|| source.error(&line[i..i + c.len_utf8()], reason))?;
+                cells.push(value);
+                x += 1;
+            }
+            match width {
+                Some(width) if width != x as usize => {
+                    return Err(source.error(line, format!("expected {width} cells")));
+                }
+                _ => width = Some(x as usize),
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        (0..self.width as i32).contains(&pos.x) && (0..self.height as i32).contains(&pos.y)
+    }
+
+    fn pos_index(&self, pos: Pos) -> Option<usize> {
+        self.in_bounds(pos)
+            .then(|| pos.x as usize + pos.y as usize * self.width)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.pos_index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.pos_index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        lines(Pos::new(0, 0), DOWN, self.height, RIGHT, width).flatten()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of the grid");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// The positions from `start_at`, included, going `direction` until
+    /// they leave the grid.
+    pub fn ray(&self, start_at: Pos, direction: Pos) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(Some(start_at), move |&pos| Some(pos + direction))
+            .take_while(|&pos| self.in_bounds(pos))
+    }
+
+    /// The in-bounds cells sharing an edge with `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4
+            .into_iter()
+            .map(move |offset| pos + offset)
+            .filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// The in-bounds cells sharing an edge or a corner with `pos`.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8
+            .into_iter()
+            .map(move |offset| pos + offset)
+            .filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// One line of text per row, each ending with `\n`.
+    pub fn render(&self, mut cell: impl FnMut(Pos, &T) -> char) -> String {
+        let mut result = String::with_capacity((self.width + 1) * self.height);
+        for (y, row) in self.rows().take(self.height).enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                result.push(cell(Pos::new(x as i32, y as i32), value));
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(value) => value,
+            None => panic!("{pos:?} is out of the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(value) => value,
+            None => panic!("{pos:?} is out of the {width}x{height} grid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#..\r\n.#.\r\n\r\n";
+
+    fn parse(text: &str) -> Result<Grid<bool>, ParseError> {
+        Grid::parse(Source::new(0, text), "expected # or .", |_, c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(
+            line(Pos::new(1, 0), DOWN, 4).collect::<Vec<_>>(),
+            [
+                Pos::new(1, 0),
+                Pos::new(1, 1),
+                Pos::new(1, 2),
+                Pos::new(1, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let lines = lines(Pos::new(0, 0), DOWN, 2, RIGHT, 2)
+            .map(|line| line.map(|pos| (pos.x, pos.y)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [[(0, 0), (1, 0)], [(0, 1), (1, 1)]]);
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = parse(TEXT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Pos::new(1, 1)]);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        let text = grid.render(|_, &b| if b { '#' } else { '.' });
+        assert_eq!(text, "#..\n.#.\n");
+        let error = parse("#..\n.x.\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+        let error = parse("#..\n.#\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let grid = parse(TEXT).unwrap();
+        assert_eq!(grid.positions().count(), 6);
+        assert_eq!(grid.row(1), [false, true, false]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(0).collect::<Vec<_>>(), [&true, &false]);
+        assert_eq!(grid.ray(Pos::new(0, 1), RIGHT).count(), 3);
+        assert_eq!(grid.neighbors4(Pos::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 5);
+        assert_eq!(grid.neighbors8(Pos::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is out of the grid")]
+    fn test_column_out_of_bounds() {
+        parse(TEXT).unwrap().column(3).count();
+    }
+
+    #[test]
+    fn test_resize_rows() {
+        let mut grid = Grid::new(2, 1, 0);
+        grid.resize_rows(3, 7);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Pos::new(1, 2)], 7);
+        grid[Pos::new(0, 2)] = 1;
+        assert_eq!(grid.row(2), [1, 7]);
+    }
+}
@@ -0,0 +1,33 @@
+use common::Vec2;
+
+/// A cell of a grid, or an offset between two cells. `y` grows downwards,
+/// like the lines of the text the grid was read from.
+pub type Pos = Vec2<i32>;
+
+pub const UP: Pos = Pos::UP;
+pub const DOWN: Pos = Pos::DOWN;
+pub const LEFT: Pos = Pos::LEFT;
+pub const RIGHT: Pos = Pos::RIGHT;
+
+/// Offsets to the cells sharing an edge.
+pub const NEIGHBORS4: [Pos; 4] = Pos::NEIGHBORS4;
+
+/// Offsets to the cells sharing an edge or a corner.
+pub const NEIGHBORS8: [Pos; 8] = Pos::NEIGHBORS8;
+
+/// `len` positions from `start_at`, each one `direction` away from the last.
+pub fn line(start_at: Pos, direction: Pos, len: usize) -> impl Iterator<Item = Pos> {
+    (0..len as i32).map(move |n| start_at + direction * n)
+}
+
+/// `len` lines of `line_len` positions. The lines start on a line going
+/// `direction` from `start_at`, and each goes `line_direction`.
+pub fn lines(
+    start_at: Pos,
+    direction: Pos,
+    len: usize,
+    line_direction: Pos,
+    line_len: usize,
+) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+    line(start_at, direction, len).map(move |line_start| line(line_start, line_direction, line_len))
+}
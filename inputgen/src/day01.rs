@@ -0,0 +1,13 @@
+use rand::Rng;
+
+/// `size` elves, each carrying a few snacks.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let elves = (0..size.max(1))
+        .map(|_| {
+            (0..rng.gen_range(1..=15))
+                .map(|_| format!("{}\n", rng.gen_range(1000..=70000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    elves.join("\n")
+}
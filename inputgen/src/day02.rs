@@ -0,0 +1,12 @@
+use rand::Rng;
+
+/// `size` rounds of the strategy guide.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let opponent = rng.gen_range(b'A'..=b'C') as char;
+            let response = rng.gen_range(b'X'..=b'Z') as char;
+            format!("{opponent} {response}\n")
+        })
+        .collect()
+}
@@ -0,0 +1,58 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const ITEMS: &[u8; 52] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `size` groups of three rucksacks. Both compartments of a rucksack share
+/// exactly one item type, and the three rucksacks of a group share exactly
+/// one badge.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size.max(1) {
+        let mut items = ITEMS.to_vec();
+        items.shuffle(rng);
+        let badge = items.pop().unwrap();
+        // Each rucksack picks from its own third of the other items, so the
+        // badge is the only item type common to the group.
+        for pool in items.chunks(items.len() / 3) {
+            result += &rucksack(rng, badge, pool);
+            result.push('\n');
+        }
+    }
+    result
+}
+
+fn rucksack(rng: &mut impl Rng, badge: u8, pool: &[u8]) -> String {
+    let mut pool = pool.to_vec();
+    pool.push(badge);
+    pool.shuffle(rng);
+    let shared = pool.pop().unwrap();
+    let (left_pool, right_pool) = pool.split_at(pool.len() / 2);
+    let len = rng.gen_range(2..=16);
+    let mut left = compartment(rng, shared, left_pool, len);
+    let mut right = compartment(rng, shared, right_pool, len);
+    // The badge must be in the rucksack even when it isn't the shared item.
+    if shared != badge {
+        let (side, pool) = if left_pool.contains(&badge) {
+            (&mut left, left_pool)
+        } else {
+            (&mut right, right_pool)
+        };
+        if !side.contains(&badge) {
+            let index = side
+                .iter()
+                .position(|&item| item != shared && pool.contains(&item))
+                .unwrap();
+            side[index] = badge;
+        }
+    }
+    String::from_utf8([left, right].concat()).unwrap()
+}
+
+/// `len` items: `shared` at least once, and at least one item of `pool`.
+fn compartment(rng: &mut impl Rng, shared: u8, pool: &[u8], len: usize) -> Vec<u8> {
+    let mut result = vec![shared];
+    result.extend((1..len).map(|_| *pool.choose(rng).unwrap()));
+    result.shuffle(rng);
+    result
+}
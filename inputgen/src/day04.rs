@@ -0,0 +1,14 @@
+use rand::Rng;
+
+/// `size` pairs of section assignments.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{},{}\n", range(rng), range(rng)))
+        .collect()
+}
+
+fn range(rng: &mut impl Rng) -> String {
+    let start = rng.gen_range(1..=99);
+    let end = rng.gen_range(start..=99);
+    format!("{start}-{end}")
+}
@@ -0,0 +1,43 @@
+use rand::Rng;
+
+/// Up to nine stacks of crates, then `size` moves. No move takes the last
+/// crate of a stack, so every stack has a top crate at the end.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let stack_count = rng.gen_range(2..=9);
+    let mut heights = (0..stack_count)
+        .map(|_| rng.gen_range(1..=8))
+        .collect::<Vec<usize>>();
+    let max_height = *heights.iter().max().unwrap();
+    let mut result = String::new();
+    for level in (0..max_height).rev() {
+        let row = heights
+            .iter()
+            .map(|&height| {
+                if level < height {
+                    format!("[{}]", rng.gen_range(b'A'..=b'Z') as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        result += row.join(" ").trim_end();
+        result.push('\n');
+    }
+    let labels = (1..=stack_count)
+        .map(|n| format!(" {n} "))
+        .collect::<Vec<_>>();
+    result += &labels.join(" ");
+    result += "\n\n";
+    for _ in 0..size {
+        let from = rng.gen_range(0..stack_count);
+        if heights[from] < 2 {
+            continue;
+        }
+        let to = (from + rng.gen_range(1..stack_count)) % stack_count;
+        let qty = rng.gen_range(1..heights[from]).min(u8::MAX as usize);
+        heights[from] -= qty;
+        heights[to] += qty;
+        result += &format!("move {qty} from {} to {}\n", from + 1, to + 1);
+    }
+    result
+}
@@ -0,0 +1,17 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// A datastream of about `size` letters. Most of it is drawn from a few
+/// letters, so markers are rare until a guaranteed one near the end.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let alphabet = rng.gen_range(3..=13);
+    let mut result = (0..size)
+        .map(|_| (b'a' + rng.gen_range(0..alphabet)) as char)
+        .collect::<String>();
+    let mut letters = (b'a'..=b'z').map(char::from).collect::<Vec<_>>();
+    letters.shuffle(rng);
+    result.extend(&letters[..14]);
+    result.extend((0..rng.gen_range(0..=size / 10)).map(|_| (b'a' + rng.gen_range(0..26)) as char));
+    result.push('\n');
+    result
+}
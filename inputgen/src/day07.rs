@@ -0,0 +1,80 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const NAMES: [&str; 12] = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"];
+const EXTENSIONS: [&str; 4] = ["", ".txt", ".dat", ".log"];
+
+/// The disk has 70000000 bytes and the update needs 30000000 free.
+const MAX_USAGE: u64 = 70000000 - 30000000;
+
+#[derive(Default)]
+struct Dir {
+    files: Vec<(String, u64)>,
+    dirs: Vec<(&'static str, Dir)>,
+}
+
+impl Dir {
+    fn total_size(&self) -> u64 {
+        let files = self.files.iter().map(|(_, size)| size).sum::<u64>();
+        files
+            + self
+                .dirs
+                .iter()
+                .map(|(_, dir)| dir.total_size())
+                .sum::<u64>()
+    }
+
+    fn write(&self, session: &mut String) {
+        session.push_str("$ ls\n");
+        for (name, _) in &self.dirs {
+            session.push_str(&format!("dir {name}\n"));
+        }
+        for (name, size) in &self.files {
+            session.push_str(&format!("{size} {name}\n"));
+        }
+        for (name, dir) in &self.dirs {
+            session.push_str(&format!("$ cd {name}\n"));
+            dir.write(session);
+            session.push_str("$ cd ..\n");
+        }
+    }
+}
+
+/// A terminal session listing a tree of at most `size` directories, each
+/// once. The files use more than the disk can spare for the update.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut remaining = size.saturating_sub(1);
+    let mut root = random_dir(rng, &mut remaining, 0);
+    let used = root.total_size();
+    if used <= MAX_USAGE {
+        let padding = MAX_USAGE - used + rng.gen_range(1..=20000000);
+        root.files.push(("padding.bin".to_string(), padding));
+    }
+    let mut session = String::from("$ cd /\n");
+    root.write(&mut session);
+    session
+}
+
+fn random_dir(rng: &mut impl Rng, remaining: &mut usize, depth: usize) -> Dir {
+    let mut names = NAMES;
+    names.shuffle(rng);
+    let dir_count = if depth < 8 {
+        rng.gen_range(0..=(*remaining).min(4))
+    } else {
+        0
+    };
+    *remaining -= dir_count;
+    let file_count = rng.gen_range(0..=6);
+    let files = names[dir_count..dir_count + file_count]
+        .iter()
+        .map(|name| {
+            let extension = EXTENSIONS.choose(rng).unwrap();
+            (format!("{name}{extension}"), rng.gen_range(1000..=350000))
+        })
+        .collect();
+    let dirs = names[..dir_count]
+        .iter()
+        .map(|&name| (name, random_dir(rng, remaining, depth + 1)))
+        .collect();
+    Dir { files, dirs }
+}
@@ -0,0 +1,15 @@
+use rand::Rng;
+
+/// A `size` by `size` forest of tree heights.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
@@ -0,0 +1,26 @@
+use rand::Rng;
+
+/// How far from the start the head may go: the solver tracks visited
+/// positions in a 1024 by 1024 bitmap centred on it.
+const REACH: i32 = 400;
+
+/// `size` motions of the head of the rope.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let (mut x, mut y) = (0i32, 0i32);
+    let mut result = String::new();
+    for _ in 0..size {
+        let steps = rng.gen_range(1..=20);
+        let (direction, dx, dy) = loop {
+            let candidate =
+                [('L', -1, 0), ('R', 1, 0), ('U', 0, -1), ('D', 0, 1)][rng.gen_range(0..4)];
+            let (_, dx, dy) = candidate;
+            if (x + dx * steps).abs() <= REACH && (y + dy * steps).abs() <= REACH {
+                break candidate;
+            }
+        };
+        x += dx * steps;
+        y += dy * steps;
+        result += &format!("{direction} {steps}\n");
+    }
+    result
+}
@@ -0,0 +1,26 @@
+use rand::Rng;
+
+/// The screen is 40 pixels wide and 6 high, one pixel per cycle.
+const SCREEN_CYCLES: usize = 240;
+
+/// A program of at least `size` instructions, lasting at least a screen.
+/// The sprite wanders around the screen without going too far off it.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut x = 1i32;
+    let mut cycles = 0;
+    let mut count = 0;
+    let mut result = String::new();
+    while count < size || cycles < SCREEN_CYCLES {
+        if rng.gen_bool(0.3) {
+            result += "noop\n";
+            cycles += 1;
+        } else {
+            let value = rng.gen_range(-(x + 5).min(20)..=(45 - x).min(20));
+            x += value;
+            result += &format!("addx {value}\n");
+            cycles += 2;
+        }
+        count += 1;
+    }
+    result
+}
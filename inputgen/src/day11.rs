@@ -0,0 +1,41 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// The divisibility tests use distinct primes. Worry levels are kept
+/// modulo their product, which must stay small enough to be squared.
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+/// Between 2 and 9 monkeys, holding `size` items between them, at least
+/// one each.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let monkey_count = rng.gen_range(2..=PRIMES.len());
+    let mut divisors = PRIMES;
+    divisors.shuffle(rng);
+    let mut items = vec![vec![]; monkey_count];
+    for i in 0..size.max(monkey_count) {
+        let monkey = if i < monkey_count {
+            i
+        } else {
+            rng.gen_range(0..monkey_count)
+        };
+        items[monkey].push(rng.gen_range(50..=99).to_string());
+    }
+    let mut monkeys = vec![];
+    for (id, items) in items.iter().enumerate() {
+        let operation = match rng.gen_range(0..5) {
+            0 => "old * old".to_string(),
+            1 | 2 => format!("old * {}", rng.gen_range(2..=19)),
+            _ => format!("old + {}", rng.gen_range(1..=8)),
+        };
+        let mut other = || (id + rng.gen_range(1..monkey_count)) % monkey_count;
+        let (if_true, if_false) = (other(), other());
+        monkeys.push(format!(
+            "Monkey {id}:\n  Starting items: {}\n  Operation: new = {operation}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {if_true}\n    \
+             If false: throw to monkey {if_false}\n",
+            items.join(", "),
+            divisors[id]
+        ));
+    }
+    monkeys.join("\n")
+}
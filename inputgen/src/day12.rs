@@ -0,0 +1,89 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// The elevations go from `a` to `z`, a path climbing them one step at a
+/// time needs at least this many squares.
+const MIN_PATH: usize = 26;
+
+/// A heightmap `size` squares wide, with a climbable path from `S` to `E`.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let width = size.max(10);
+    let height = (width / 4).max(5);
+    let mut grid = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| rng.gen_range(b'a'..=b'z'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let target = rng.gen_range(MIN_PATH..=(width * height / 2).max(MIN_PATH));
+    let path = random_path(rng, width, height, target).unwrap_or_else(|| snake(width, height));
+    let last = path.len() - 1;
+    for (i, &(x, y)) in path.iter().enumerate() {
+        grid[y][x] = b'a' + (25 * i / last) as u8;
+    }
+    let (x, y) = path[0];
+    grid[y][x] = b'S';
+    let (x, y) = path[last];
+    grid[y][x] = b'E';
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+/// A path without loops of at least `MIN_PATH` squares, and of `target`
+/// squares if a randomised depth-first search finds one.
+fn random_path(
+    rng: &mut impl Rng,
+    width: usize,
+    height: usize,
+    target: usize,
+) -> Option<Vec<(usize, usize)>> {
+    let mut visited = vec![vec![false; width]; height];
+    let start = (rng.gen_range(0..width), rng.gen_range(0..height));
+    visited[start.1][start.0] = true;
+    let mut stack = vec![start];
+    let mut longest = vec![];
+    while let Some(&(x, y)) = stack.last() {
+        if stack.len() >= target {
+            return Some(stack);
+        }
+        let mut next = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let x = x.checked_add_signed(dx).filter(|&x| x < width)?;
+                let y = y.checked_add_signed(dy).filter(|&y| y < height)?;
+                (!visited[y][x]).then_some((x, y))
+            })
+            .collect::<Vec<_>>();
+        next.shuffle(rng);
+        match next.first() {
+            Some(&(x, y)) => {
+                visited[y][x] = true;
+                stack.push((x, y));
+            }
+            None => {
+                if stack.len() > longest.len() {
+                    longest = stack.clone();
+                }
+                stack.pop();
+            }
+        }
+    }
+    (longest.len() >= MIN_PATH).then_some(longest)
+}
+
+/// Every square, going back and forth along the rows.
+fn snake(width: usize, height: usize) -> Vec<(usize, usize)> {
+    (0..height)
+        .flat_map(|y| {
+            (0..width).map(move |x| {
+                if y % 2 == 0 {
+                    (x, y)
+                } else {
+                    (width - 1 - x, y)
+                }
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,31 @@
+use rand::Rng;
+
+const DIVIDERS: [&str; 2] = ["[[2]]", "[[6]]"];
+
+/// `size` pairs of distinct packets, none of them a divider packet.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut pairs = vec![];
+    while pairs.len() < size {
+        let (left, right) = (packet(rng, 0), packet(rng, 0));
+        if left != right
+            && !DIVIDERS.contains(&left.as_str())
+            && !DIVIDERS.contains(&right.as_str())
+        {
+            pairs.push(format!("{left}\n{right}\n"));
+        }
+    }
+    pairs.join("\n")
+}
+
+fn packet(rng: &mut impl Rng, depth: usize) -> String {
+    let items = (0..rng.gen_range(0..=5))
+        .map(|_| {
+            if depth < 4 && rng.gen_bool(0.3) {
+                packet(rng, depth + 1)
+            } else {
+                rng.gen_range(0..=10).to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
@@ -0,0 +1,27 @@
+use rand::Rng;
+
+/// `size` paths of rock, below and around the point sand pours from.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut x = rng.gen_range(470..=530);
+            let mut y = rng.gen_range(10..=170);
+            let mut vertices = vec![format!("{x},{y}")];
+            let mut horizontal = rng.gen_bool(0.5);
+            for _ in 0..rng.gen_range(1..=5) {
+                let len = rng.gen_range(1..=12);
+                let sign = if rng.gen_bool(0.5) { 1 } else { -1 };
+                if horizontal {
+                    x += sign * len;
+                } else if y - len < 10 {
+                    y += len;
+                } else {
+                    y += sign * len;
+                }
+                horizontal = !horizontal;
+                vertices.push(format!("{x},{y}"));
+            }
+            vertices.join(" -> ") + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,48 @@
+use rand::Rng;
+
+/// The search area of the real puzzle.
+pub const MAX_POS: i64 = 4000000;
+
+/// About `size` sensors and 4 more, leaving a single position of the
+/// `0..=MAX_POS` square where the distress beacon can be.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    generate_in(rng, size, MAX_POS)
+}
+
+/// Same as `generate`, with a `0..=max_pos` search area, `max_pos` being
+/// at least 2.
+///
+/// No sensor detects the hidden beacon, because each one's closest beacon
+/// is nearer. Sensors in the corners of the area cover all of it but the
+/// hidden beacon: every position lies between it and a corner.
+pub fn generate_in(rng: &mut impl Rng, size: usize, max_pos: i64) -> String {
+    let hidden = (rng.gen_range(1..max_pos), rng.gen_range(1..max_pos));
+    let mut sensors = vec![(0, 0), (max_pos, 0), (0, max_pos), (max_pos, max_pos)];
+    // Small areas may not have room for `size` more sensors.
+    for _ in 0..size * 10 {
+        if sensors.len() == size + 4 {
+            break;
+        }
+        let sensor = (rng.gen_range(0..=max_pos), rng.gen_range(0..=max_pos));
+        if manhattan(sensor, hidden) >= 2 && !sensors.contains(&sensor) {
+            sensors.push(sensor);
+        }
+    }
+    let mut result = String::new();
+    for (x, y) in sensors {
+        let dist = manhattan((x, y), hidden) - 1;
+        // Any position at `dist` from the sensor will do.
+        let dx = rng.gen_range(-dist..=dist);
+        let dy = (dist - dx.abs()) * if rng.gen_bool(0.5) { 1 } else { -1 };
+        result += &format!(
+            "Sensor at x={x}, y={y}: closest beacon is at x={}, y={}\n",
+            x + dx,
+            y + dy
+        );
+    }
+    result
+}
+
+fn manhattan((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> i64 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
@@ -0,0 +1,62 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// The solver keeps sets of working valves in a `u32`, and tries all of
+/// their splits between two agents.
+const MAX_WORKING: usize = 15;
+
+/// A connected cave of `size` valves, `AA` being one of the stuck ones.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.clamp(2, 60);
+    let mut names = vec!["AA".to_string()];
+    while names.len() < size {
+        let name = (0..2)
+            .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+            .collect::<String>();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let working = rng.gen_range(1..=(size - 1).min(MAX_WORKING));
+    let mut rates = (0..size)
+        .map(|i| {
+            if (1..=working).contains(&i) {
+                rng.gen_range(1..=25)
+            } else {
+                0
+            }
+        })
+        .collect::<Vec<_>>();
+    rates[1..].shuffle(rng);
+    // A random spanning tree keeps the cave connected, then a few more
+    // tunnels make loops.
+    let mut tunnels = vec![vec![]; size];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..size {
+        connect(i, rng.gen_range(0..i));
+    }
+    for _ in 0..size / 3 {
+        connect(rng.gen_range(0..size), rng.gen_range(0..size));
+    }
+    let mut lines = (0..size)
+        .map(|i| {
+            let others = tunnels[i]
+                .iter()
+                .map(|&j| names[j].as_str())
+                .collect::<Vec<_>>();
+            let tunnels = if others.len() == 1 {
+                format!("tunnel leads to valve {}", others[0])
+            } else {
+                format!("tunnels lead to valves {}", others.join(", "))
+            };
+            format!("Valve {} has flow rate={}; {tunnels}\n", names[i], rates[i])
+        })
+        .collect::<Vec<_>>();
+    lines.shuffle(rng);
+    lines.concat()
+}
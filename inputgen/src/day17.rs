@@ -0,0 +1,10 @@
+use rand::Rng;
+
+/// A jet pattern of `size` pushes.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut result = (0..size.max(1))
+        .map(|_| if rng.gen_bool(0.5) { '<' } else { '>' })
+        .collect::<String>();
+    result.push('\n');
+    result
+}
@@ -0,0 +1,25 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+
+/// About `size` distinct cubes, clumped together so the droplet has air
+/// pockets.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let side = ((size as f64).cbrt() * 1.4).ceil().max(2.0) as i32;
+    let mut cubes = HashSet::new();
+    let mut result = String::new();
+    for _ in 0..size.max(1) * 4 {
+        if cubes.len() == size.max(1) {
+            break;
+        }
+        let cube = (
+            rng.gen_range(1..=side),
+            rng.gen_range(1..=side),
+            rng.gen_range(1..=side),
+        );
+        if cubes.insert(cube) {
+            result += &format!("{},{},{}\n", cube.0, cube.1, cube.2);
+        }
+    }
+    result
+}
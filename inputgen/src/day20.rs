@@ -0,0 +1,22 @@
+use rand::Rng;
+
+/// An encrypted file of `size` numbers, exactly one of them 0.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    let zero = rng.gen_range(0..size);
+    (0..size)
+        .map(|i| {
+            let number = if i == zero {
+                0
+            } else {
+                loop {
+                    let n = rng.gen_range(-10000..=10000);
+                    if n != 0 {
+                        break n;
+                    }
+                }
+            };
+            format!("{number}\n")
+        })
+        .collect()
+}
@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// The jobs of a troop of monkeys, about `size` levels of operations deep
+/// between `root` and `humn`.
+///
+/// The monkeys on the way from `humn` to `root` are built bottom-up, keeping
+/// track of what they yell both for `humn`'s listed number and for the one
+/// part 2 should find, so every division stays exact. The other side of
+/// `root` is built top-down to yell the same as `humn`'s side does with
+/// that second number.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut troop = Troop {
+        names: HashSet::from(["root".to_string(), "humn".to_string()]),
+        jobs: vec![],
+    };
+    let listed: i64 = rng.gen_range(1..=1000);
+    let wanted: i64 = rng.gen_range(1..=1000);
+    let (mut name, mut values) = ("humn".to_string(), (listed, wanted));
+    troop.jobs.push(format!("humn: {listed}"));
+    for _ in 0..size {
+        let (left, right, op, next) = loop {
+            let (a, b) = values;
+            match rng.gen_range(0..4) {
+                0 => {
+                    let n = rng.gen_range(1..=1000);
+                    break (None, Some(n), '+', (a + n, b + n));
+                }
+                1 if rng.gen_bool(0.5) => {
+                    let n = rng.gen_range(1..=1000);
+                    break (None, Some(n), '-', (a - n, b - n));
+                }
+                1 => {
+                    let n = rng.gen_range(1..=1000);
+                    break (Some(n), None, '-', (n - a, n - b));
+                }
+                2 if a.abs().max(b.abs()) < 1_000_000_000 => {
+                    let n = rng.gen_range(2..=9);
+                    break (None, Some(n), '*', (a * n, b * n));
+                }
+                3 => {
+                    let n = gcd(a, b);
+                    if n > 1 {
+                        break (None, Some(n), '/', (a / n, b / n));
+                    }
+                }
+                _ => {}
+            }
+        };
+        // Which side `humn`'s monkey goes is left to the order of the
+        // operands, any other side gets a new monkey yelling `n`.
+        let left = match left {
+            Some(n) => troop.yell(rng, n, 3),
+            None => name.clone(),
+        };
+        let right = match right {
+            Some(n) => troop.yell(rng, n, 3),
+            None => name.clone(),
+        };
+        name = troop.new_name(rng);
+        troop.jobs.push(format!("{name}: {left} {op} {right}"));
+        values = next;
+    }
+    let other = troop.yell(rng, values.1, 4);
+    let (left, right) = if rng.gen_bool(0.5) {
+        (name, other)
+    } else {
+        (other, name)
+    };
+    troop.jobs.push(format!("root: {left} + {right}"));
+    troop.jobs.shuffle(rng);
+    troop.jobs.join("\n") + "\n"
+}
+
+struct Troop {
+    names: HashSet<String>,
+    jobs: Vec<String>,
+}
+
+impl Troop {
+    fn new_name(&mut self, rng: &mut impl Rng) -> String {
+        loop {
+            let name = (0..4)
+                .map(|_| rng.gen_range(b'a'..=b'z') as char)
+                .collect::<String>();
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// Adds monkeys, at most `depth` levels of them, the top one yelling
+    /// `value`. Returns its name.
+    fn yell(&mut self, rng: &mut impl Rng, value: i64, depth: usize) -> String {
+        let name = self.new_name(rng);
+        let job = if depth == 0 || (value > 0 && value < 20 && rng.gen_bool(0.5)) {
+            if value >= 0 {
+                value.to_string()
+            } else {
+                let n = rng.gen_range(1..=10);
+                let small = self.yell(rng, n, 0);
+                format!("{small} - {}", self.yell(rng, n - value, 0))
+            }
+        } else {
+            let divisor = (2..=9).find(|d| value % d == 0 && value != 0);
+            match (rng.gen_range(0..3), divisor) {
+                (0, Some(d)) => format!(
+                    "{} * {}",
+                    self.yell(rng, value / d, depth - 1),
+                    self.yell(rng, d, depth - 1)
+                ),
+                (1, _) if value.abs() < 1_000_000_000 => {
+                    let d = rng.gen_range(2..=5);
+                    format!(
+                        "{} / {}",
+                        self.yell(rng, value * d, depth - 1),
+                        self.yell(rng, d, depth - 1)
+                    )
+                }
+                _ => {
+                    let n = rng.gen_range(1..=100);
+                    format!(
+                        "{} - {}",
+                        self.yell(rng, value + n, depth - 1),
+                        self.yell(rng, n, depth - 1)
+                    )
+                }
+            }
+        };
+        self.jobs.push(format!("{name}: {job}"));
+        name
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,130 @@
+//! Random but valid puzzle inputs, to stress the solvers and compare
+//! alternative implementations on more than the sample and real inputs.
+//!
+//! Each day has a module with a `generate(rng, size)` function. What `size`
+//! counts depends on the day: lines, elves, monkeys, valves...
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day20;
+pub mod day21;
+pub mod day25;
+
+/// The random number generator every generator is driven by. Its output
+/// for a given seed is stable across platforms and versions.
+pub type Random = ChaCha8Rng;
+
+pub fn random(seed: u64) -> Random {
+    Random::seed_from_u64(seed)
+}
+
+pub struct Generator {
+    pub day: u8,
+    /// A size giving inputs about as big as the real ones.
+    pub default_size: usize,
+    pub generate: fn(&mut Random, usize) -> String,
+}
+
+impl Generator {
+    const fn new(day: u8, default_size: usize, generate: fn(&mut Random, usize) -> String) -> Self {
+        Self {
+            day,
+            default_size,
+            generate,
+        }
+    }
+
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut random(seed), size)
+    }
+}
+
+pub const GENERATORS: [Generator; 21] = [
+    Generator::new(1, 250, day01::generate),
+    Generator::new(2, 2500, day02::generate),
+    Generator::new(3, 100, day03::generate),
+    Generator::new(4, 1000, day04::generate),
+    Generator::new(5, 500, day05::generate),
+    Generator::new(6, 4000, day06::generate),
+    Generator::new(7, 180, day07::generate),
+    Generator::new(8, 99, day08::generate),
+    Generator::new(9, 2000, day09::generate),
+    Generator::new(10, 146, day10::generate),
+    Generator::new(11, 36, day11::generate),
+    Generator::new(12, 160, day12::generate),
+    Generator::new(13, 150, day13::generate),
+    Generator::new(14, 60, day14::generate),
+    Generator::new(15, 24, day15::generate),
+    Generator::new(16, 55, day16::generate),
+    Generator::new(17, 10000, day17::generate),
+    Generator::new(18, 2800, day18::generate),
+    Generator::new(20, 5000, day20::generate),
+    Generator::new(21, 60, day21::generate),
+    Generator::new(25, 120, day25::generate),
+];
+
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        for generator in &GENERATORS {
+            let size = generator.default_size / 4;
+            assert_eq!(generator.generate(7, size), generator.generate(7, size));
+            assert_ne!(generator.generate(7, size), generator.generate(8, size));
+        }
+    }
+
+    #[test]
+    fn test_day06_markers() {
+        for seed in 0..50 {
+            let input = day06::generate(&mut random(seed), 1000);
+            let stream = ::day06::parse(&input).unwrap();
+            assert_eq!(
+                ::day06::solve::<4>(stream),
+                ::day06::solve_faster::<4>(stream)
+            );
+            assert_eq!(
+                ::day06::solve::<14>(stream),
+                ::day06::solve_faster::<14>(stream)
+            );
+        }
+    }
+
+    #[test]
+    fn test_day15_hidden_beacon() {
+        for seed in 0..20 {
+            let input = day15::generate_in(&mut random(seed), 10, 40);
+            let sensors = ::day15::parse(&input).unwrap();
+            let max_pos = ::day15::Pos::new(40, 40);
+            assert_eq!(
+                ::day15::part2(&sensors, max_pos),
+                ::day15::better_part2(&sensors, max_pos)
+            );
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{Format, Frame, Recorder};
+
+/// Saves each frame as a numbered PPM or PNG file of a directory:
+/// `frame00000.png`, `frame00001.png`...
+pub struct Images {
+    dir: PathBuf,
+    png: bool,
+    scale: usize,
+    count: usize,
+}
+
+impl Images {
+    /// Creates `dir` if needed. `format` must be `Ppm` or `Png`.
+    pub fn new(dir: &Path, format: Format, scale: usize) -> io::Result<Self> {
+        assert!(matches!(format, Format::Ppm | Format::Png));
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            png: format == Format::Png,
+            scale: scale.max(1),
+            count: 0,
+        })
+    }
+
+    fn path(&self, index: usize) -> PathBuf {
+        let extension = if self.png { "png" } else { "ppm" };
+        self.dir.join(format!("frame{index:05}.{extension}"))
+    }
+}
+
+impl Recorder for Images {
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        let width = frame.width() * self.scale;
+        let height = frame.height() * self.scale;
+        let rgb = frame.to_rgb(self.scale);
+        let mut out = BufWriter::new(File::create(self.path(self.count))?);
+        if self.png {
+            let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.write_header()?.write_image_data(&rgb)?;
+        } else {
+            write!(out, "P6\n{width} {height}\n255\n")?;
+            out.write_all(&rgb)?;
+        }
+        out.flush()?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Color};
+    use grid::Pos;
+
+    #[test]
+    fn test_images() {
+        let dir = std::env::temp_dir().join("visual-images");
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut frame = Frame::new(2, 1);
+        frame.set(Pos::new(0, 0), Cell::new('#', Color::WHITE));
+        let mut ppm = Images::new(&dir, Format::Ppm, 1).unwrap();
+        ppm.record(&frame).unwrap();
+        ppm.record(&frame).unwrap();
+        let bytes = std::fs::read(dir.join("frame00001.ppm")).unwrap();
+        assert_eq!(bytes, b"P6\n2 1\n255\n\xff\xff\xff\0\0\0");
+        let mut png = Images::new(&dir, Format::Png, 3).unwrap();
+        png.record(&frame).unwrap();
+        let bytes = std::fs::read(dir.join("frame00000.png")).unwrap();
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+}
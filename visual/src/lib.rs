@@ -0,0 +1,328 @@
+//! Pictures of the puzzles while they are being solved: a day draws
+//! `Frame`s and hands them to a `Recorder`, which prints them as text,
+//! animates them in the terminal, or saves them as images.
+
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use grid::{Grid, Pos};
+
+mod image;
+mod terminal;
+
+pub use image::Images;
+pub use terminal::{Ansi, Ascii};
+
+/// A 24-bit colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const DARK_GREY: Color = Color(60, 60, 60);
+    pub const GREY: Color = Color(140, 140, 140);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const RED: Color = Color(230, 60, 50);
+    pub const GREEN: Color = Color(70, 200, 80);
+    pub const BLUE: Color = Color(60, 120, 230);
+    pub const YELLOW: Color = Color(240, 200, 60);
+
+    /// The colour `t` of the way from `self` to `other`, `t` being
+    /// clamped to `0.0..=1.0`.
+    pub fn mix(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell::new(' ', Color::BLACK);
+
+    pub const fn new(ch: char, color: Color) -> Self {
+        Self { ch, color }
+    }
+}
+
+/// A picture made of coloured characters. Text output shows the
+/// characters, images show each cell as a square of its colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, Cell::EMPTY)
+    }
+
+    pub fn filled(width: usize, height: usize, cell: Cell) -> Self {
+        Self {
+            cells: Grid::new(width, height, cell),
+        }
+    }
+
+    /// A frame the size of `grid`, with a cell drawn by `cell` for each of
+    /// its values.
+    pub fn from_grid<T>(grid: &Grid<T>, mut cell: impl FnMut(Pos, &T) -> Cell) -> Self {
+        let mut result = Self::new(grid.width(), grid.height());
+        for pos in grid.positions() {
+            result.cells[pos] = cell(pos, &grid[pos]);
+        }
+        result
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<Cell> {
+        self.cells.get(pos).copied()
+    }
+
+    /// Draws `cell` at `pos`, unless it is outside the frame.
+    pub fn set(&mut self, pos: Pos, cell: Cell) {
+        if let Some(target) = self.cells.get_mut(pos) {
+            *target = cell;
+        }
+    }
+
+    /// The characters, one line per row.
+    pub fn to_ascii(&self) -> String {
+        self.cells.render(|_, cell| cell.ch)
+    }
+
+    /// The characters coloured with ANSI escape codes, one line per row.
+    pub fn to_ansi(&self) -> String {
+        let mut result = String::with_capacity(self.width() * self.height() * 4);
+        for row in self.cells.rows().take(self.height()) {
+            let mut current = None;
+            for cell in row {
+                if current != Some(cell.color) {
+                    let Color(r, g, b) = cell.color;
+                    result += &format!("\x1b[38;2;{r};{g};{b}m");
+                    current = Some(cell.color);
+                }
+                result.push(cell.ch);
+            }
+            result += "\x1b[0m\n";
+        }
+        result
+    }
+
+    /// RGB bytes, row by row, of the frame drawn with `scale` pixels per
+    /// cell side.
+    pub fn to_rgb(&self, scale: usize) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.width() * self.height() * scale * scale * 3);
+        for row in self.cells.rows().take(self.height()) {
+            for _ in 0..scale {
+                for cell in row {
+                    let Color(r, g, b) = cell.color;
+                    for _ in 0..scale {
+                        result.extend([r, g, b]);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Where the frames of a visualization go.
+pub trait Recorder {
+    fn record(&mut self, frame: &Frame) -> io::Result<()>;
+
+    /// Called once the last frame is recorded.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps the frames, mostly for tests.
+impl Recorder for Vec<Frame> {
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        self.push(frame.clone());
+        Ok(())
+    }
+}
+
+impl<R: Recorder + ?Sized> Recorder for Box<R> {
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        (**self).record(frame)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Records every `every`th frame to the inner recorder, and the last one.
+pub struct Sample<R> {
+    inner: R,
+    every: usize,
+    count: usize,
+    skipped: Option<Frame>,
+}
+
+impl<R: Recorder> Sample<R> {
+    pub fn new(inner: R, every: usize) -> Self {
+        Self {
+            inner,
+            every: every.max(1),
+            count: 0,
+            skipped: None,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Recorder> Recorder for Sample<R> {
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        let result = if self.count.is_multiple_of(self.every) {
+            self.skipped = None;
+            self.inner.record(frame)
+        } else {
+            self.skipped = Some(frame.clone());
+            Ok(())
+        };
+        self.count += 1;
+        result
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(frame) = self.skipped.take() {
+            self.inner.record(&frame)?;
+        }
+        self.inner.finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Plain text frames on the standard output.
+    Ascii,
+    /// Coloured frames redrawn in place on the terminal.
+    Ansi,
+    Ppm,
+    Png,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ascii" => Some(Self::Ascii),
+            "ansi" => Some(Self::Ansi),
+            "ppm" => Some(Self::Ppm),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+}
+
+/// How to record a visualization.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub format: Format,
+    /// Only every `every`th frame is recorded, and the last one.
+    pub every: usize,
+    /// The pause after each frame of an ANSI animation.
+    pub delay: Duration,
+    /// The directory image files are written to.
+    pub dir: PathBuf,
+    /// The side of a cell in image pixels.
+    pub scale: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            format: Format::Ansi,
+            every: 1,
+            delay: Duration::from_millis(50),
+            dir: "frames".into(),
+            scale: 4,
+        }
+    }
+}
+
+impl Options {
+    /// A recorder writing the frames as the options say.
+    pub fn recorder(&self) -> io::Result<Box<dyn Recorder>> {
+        let inner: Box<dyn Recorder> = match self.format {
+            Format::Ascii => Box::new(Ascii::new(io::stdout())),
+            Format::Ansi => Box::new(Ansi::new(io::stdout(), self.delay)),
+            Format::Ppm | Format::Png => Box::new(Images::new(&self.dir, self.format, self.scale)?),
+        };
+        Ok(Box::new(Sample::new(inner, self.every)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(text: &str) -> Frame {
+        let lines = text.lines().collect::<Vec<_>>();
+        let mut result = Frame::new(lines[0].len(), lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                let color = if ch == '#' {
+                    Color::WHITE
+                } else {
+                    Color::BLACK
+                };
+                result.set(Pos::new(x as i32, y as i32), Cell::new(ch, color));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = frame("#.\n.#\n");
+        assert_eq!(frame.to_ascii(), "#.\n.#\n");
+        assert_eq!(
+            frame.to_ansi(),
+            "\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m.\x1b[0m\n\
+             \x1b[38;2;0;0;0m.\x1b[38;2;255;255;255m#\x1b[0m\n"
+        );
+        let rgb = frame.to_rgb(2);
+        assert_eq!(rgb.len(), 4 * 4 * 3);
+        // the second pixel row is still the first cell row
+        assert_eq!(
+            &rgb[12..24],
+            [255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(Color::BLACK.mix(Color::WHITE, 0.5), Color(128, 128, 128));
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut sample = Sample::new(vec![], 3);
+        for text in ["a\n", "b\n", "c\n", "d\n", "e\n"] {
+            sample.record(&frame(text)).unwrap();
+        }
+        sample.finish().unwrap();
+        let frames = sample.into_inner();
+        let texts = frames.iter().map(Frame::to_ascii).collect::<Vec<_>>();
+        assert_eq!(texts, ["a\n", "d\n", "e\n"]);
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::{Frame, Recorder};
+
+/// Writes each frame as plain text, followed by a blank line.
+pub struct Ascii<W> {
+    out: W,
+}
+
+impl<W: Write> Ascii<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Recorder for Ascii<W> {
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        writeln!(self.out, "{}", frame.to_ascii())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// Draws each frame in colour over the previous one, from the top left
+/// corner of the terminal, pausing `delay` after each.
+pub struct Ansi<W> {
+    out: W,
+    delay: Duration,
+    started: bool,
+}
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+impl<W: Write> Ansi<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Self {
+            out,
+            delay,
+            started: false,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Recorder for Ansi<W> {
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        if !self.started {
+            write!(self.out, "{CLEAR_SCREEN}{HIDE_CURSOR}")?;
+            self.started = true;
+        }
+        write!(self.out, "{CURSOR_HOME}{}", frame.to_ansi())?;
+        self.out.flush()?;
+        std::thread::sleep(self.delay);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.started {
+            write!(self.out, "{SHOW_CURSOR}")?;
+        }
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Color};
+    use grid::Pos;
+
+    #[test]
+    fn test_terminal() {
+        let mut frame = Frame::new(2, 1);
+        frame.set(Pos::new(1, 0), Cell::new('#', Color::RED));
+        let mut ascii = Ascii::new(vec![]);
+        ascii.record(&frame).unwrap();
+        ascii.record(&frame).unwrap();
+        assert_eq!(ascii.into_inner(), b" #\n\n #\n\n");
+        let mut ansi = Ansi::new(vec![], Duration::ZERO);
+        ansi.record(&frame).unwrap();
+        ansi.finish().unwrap();
+        let text = String::from_utf8(ansi.into_inner()).unwrap();
+        assert!(text.starts_with("\x1b[2J\x1b[?25l\x1b[H\x1b[38;2;0;0;0m \x1b[38;2;230;60;50m#"));
+        assert!(text.ends_with("\x1b[0m\n\x1b[?25h"));
+    }
+}